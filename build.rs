@@ -0,0 +1,133 @@
+//! Generates the registry of solutions that are linked into the main binary.
+//! Every `src/bin/<year>-<day>.rs` is included as a module, so `cargo all` and `cargo time` can call them in-process.
+//!
+//! With `test_lib`, the built-in scaffold templates are rendered as well, so the library tests can check that they build and run.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/template.txt");
+    println!("cargo:rerun-if-changed=src/templates");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut puzzles: Vec<(u16, u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| {
+                    let (year, day) = path.file_stem()?.to_str()?.split_once('-')?;
+                    let year: u16 = year.parse().ok().filter(|y| *y >= 2015)?;
+                    let day: u8 = day.parse().ok().filter(|d| (1..=25).contains(d))?;
+                    Some((year, day, path.to_string_lossy().replace('\\', "/")))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let modules: String = puzzles
+        .iter()
+        .map(|(year, day, path)| {
+            format!(
+                "    #[path = \"{path}\"]\n    #[allow(warnings, clippy::all, clippy::pedantic)]\n    mod day_{year}_{day:02};\n"
+            )
+        })
+        .collect();
+
+    let entries: String = puzzles
+        .iter()
+        .map(|(year, day, _)| {
+            format!(
+                "        DaySolution {{ puzzle: advent_of_code::puzzle!({year}, {day}), parts: day_{year}_{day:02}::__PARTS, run: day_{year}_{day:02}::__run_in_process }},\n"
+            )
+        })
+        .collect();
+
+    // NOTE: solutions are left out of test builds, their tests already run as part of the bins.
+    // they are also left out with `dhat-heap`, as every solution declares its own global allocator then.
+    let registry = format!(
+        "#[cfg(not(any(test, feature = \"dhat-heap\")))]
+mod days {{
+    use advent_of_code::template::registry::DaySolution;
+
+{modules}
+    pub static DAYS: &[DaySolution] = &[
+{entries}    ];
+}}
+
+#[cfg(any(test, feature = \"dhat-heap\"))]
+mod days {{
+    use advent_of_code::template::registry::DaySolution;
+
+    pub static DAYS: &[DaySolution] = &[];
+}}
+"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out_path, registry).unwrap();
+
+    render_templates(Path::new(&manifest_dir));
+}
+
+/// Render the built-in templates as day 2015-01 into `templates.rs`, without their tests. Used by the tests of `scaffold`.
+fn render_templates(manifest_dir: &Path) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut templates = vec![];
+
+    // NOTE: with `dhat-heap`, every rendered solution would declare another global allocator.
+    if env::var_os("CARGO_FEATURE_TEST_LIB").is_some()
+        && env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none()
+    {
+        templates.push(("default".to_string(), manifest_dir.join("src/template.txt")));
+        if let Ok(entries) = fs::read_dir(manifest_dir.join("src/templates")) {
+            for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    templates.push((name.to_string(), path.clone()));
+                }
+            }
+        }
+        templates.sort();
+    }
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (name, path) in &templates {
+        let template = fs::read_to_string(path).unwrap();
+        let code = template
+            .split("#[cfg(test)]")
+            .next()
+            .unwrap()
+            .replace("%YEAR%", "2015")
+            .replace("%DAY_NUMBER%", "1")
+            .replace("%DAY%", "01")
+            .replace("%PUZZLE%", "2015-01")
+            .replace("%TITLE%", "Day 1")
+            .replace("%URL%", "https://adventofcode.com/2015/day/1");
+
+        let rendered = Path::new(&out_dir).join(format!("template-{name}.rs"));
+        fs::write(&rendered, code).unwrap();
+
+        let rendered = rendered.to_string_lossy().replace('\\', "/");
+        modules.push_str(&format!(
+            "#[path = \"{rendered}\"]\n#[allow(warnings, clippy::all, clippy::pedantic)]\nmod template_{name};\n"
+        ));
+        entries.push_str(&format!(
+            "    (\"{name}\", template_{name}::__run_in_process),\n"
+        ));
+    }
+
+    let registry = format!(
+        "{modules}
+pub static TEMPLATES: &[(&str, crate::template::registry::RunSolution)] = &[
+{entries}];
+"
+    );
+    fs::write(Path::new(&out_dir).join("templates.rs"), registry).unwrap();
+}
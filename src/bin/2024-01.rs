@@ -1,14 +1,16 @@
 use std::collections::BTreeMap;
 
-advent_of_code::solution!(1);
+use advent_of_code::ints::ints;
+
+advent_of_code::solution!(2024, 1);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut left = vec![];
     let mut right = vec![];
     input.lines().for_each(|line| {
-        let mut cols = line.split_ascii_whitespace();
-        left.push(cols.next().unwrap().parse::<u32>().unwrap());
-        right.push(cols.next().unwrap().parse::<u32>().unwrap());
+        let [l, r] = ints::<u32, 2>(line).unwrap();
+        left.push(l);
+        right.push(r);
     });
     left.sort();
     right.sort();
@@ -23,9 +25,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     let mut left = vec![];
     let mut right = BTreeMap::<u32, u32>::new();
     input.lines().for_each(|line| {
-        let mut cols = line.split_ascii_whitespace();
-        left.push(cols.next().unwrap().parse::<u32>().unwrap());
-        let k = cols.next().unwrap().parse().unwrap();
+        let [l, k] = ints::<u32, 2>(line).unwrap();
+        left.push(l);
         right.entry(k).and_modify(|v| *v += 1).or_insert(1);
     });
     let mut simularity = 0;
@@ -43,13 +44,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(31));
     }
 }
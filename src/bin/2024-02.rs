@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut safe = 0;
@@ -105,13 +105,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use regex::Regex;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let re = Regex::new(r"mul\((?<d1>[0-9]{1,})\,(?<d2>[0-9]{1,})\)").unwrap();
@@ -59,18 +59,5 @@ fn build_ignores(dos: &Vec<Range<usize>>, donts: &Vec<Range<usize>>) -> Vec<Rang
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result =
-            part_two("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))");
-        assert_eq!(result, Some(48));
-    }
+    advent_of_code::example_tests!(default, second);
 }
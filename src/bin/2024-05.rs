@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use advent_of_code::template::Solution;
+use itertools::Itertools;
+use tracing::instrument;
+
+advent_of_code::solution!(2024, 5, Day05);
+
+struct Day05;
+
+fn parse_input(input: &str) -> (Vec<(&str, &str)>, Vec<Vec<&str>>) {
+    let (rules, pages) = input.split_once("\n\n").expect("two parts");
+    let rules = rules
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once("|").expect("a | b");
+            (a, b)
+        })
+        .collect_vec();
+    let pages = pages
+        .lines()
+        .map(|line| line.split(",").collect_vec())
+        .collect_vec();
+    (rules, pages)
+}
+
+impl Solution for Day05 {
+    type Parsed<'a> = (Vec<(&'a str, &'a str)>, Vec<Vec<&'a str>>);
+    type PartOne = Option<u32>;
+    type PartTwo = Option<u32>;
+
+    fn parse(input: &str) -> miette::Result<Self::Parsed<'_>> {
+        Ok(parse_input(input))
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Self::PartOne {
+        let (rules, pages) = parsed;
+        let mut sum = 0u32;
+        for update in pages {
+            let sorted: Vec<&str> = update
+                .clone()
+                .iter()
+                .map(|s| *s)
+                .sorted_by(|a, b| {
+                    if rules.contains(&(*a, *b)) {
+                        Ordering::Less
+                    } else if rules.contains(&(*b, *a)) {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .collect();
+            if *update == sorted {
+                sum += update
+                    .get(update.len() / 2)
+                    .expect("midpoint")
+                    .parse::<u32>()
+                    .expect("parse midpoint");
+            }
+        }
+        Some(sum)
+    }
+
+    #[instrument(skip_all)]
+    fn part_two(parsed: &Self::Parsed<'_>) -> Self::PartTwo {
+        let (rules, pages) = parsed;
+        // complete rewrite for part 2 because my solution for 1 sucked.
+        // we can turn the ordering rules (a|b) into a comparator and just sort
+        let mut sum = 0u32;
+        for update in pages {
+            let sorted: Vec<&str> = update
+                .clone()
+                .iter()
+                .map(|s| *s)
+                .sorted_by(|a, b| {
+                    if rules.contains(&(*a, *b)) {
+                        Ordering::Less
+                    } else if rules.contains(&(*b, *a)) {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .collect();
+            if *update != sorted {
+                sum += sorted
+                    .get(sorted.len() / 2)
+                    .expect("midpoint")
+                    .parse::<u32>()
+                    .expect("parse midpoint");
+            }
+        }
+        Some(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = Day05::part_one(&Day05::parse(&input).unwrap());
+        assert_eq!(result, Some(143));
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = Day05::part_two(&Day05::parse(&input).unwrap());
+        assert_eq!(result, Some(123));
+    }
+}
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2024, 7);
 
 fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
     input
@@ -89,13 +89,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(11387));
     }
 }
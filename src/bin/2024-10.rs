@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use advent_of_code::grid::Grid;
+use glam::IVec2;
+
+advent_of_code::solution!(2024, 10);
+
+#[derive(Debug)]
+struct TrailSystem {
+    trailheads: Vec<IVec2>,
+    trails: Grid<u32>,
+}
+
+fn read_trails(input: &str) -> TrailSystem {
+    let trails = Grid::parse(input, |c| c.to_digit(10)).unwrap();
+    let trailheads = trails.find_all(|&height| height == 0).collect();
+
+    TrailSystem { trailheads, trails }
+}
+
+struct Hiker {}
+
+impl Hiker {
+    /// Positions of height `n` that are reachable from any of `positions`.
+    fn climb<'a>(
+        trails: &'a Grid<u32>,
+        positions: impl IntoIterator<Item = IVec2> + 'a,
+        n: u32,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        positions.into_iter().flat_map(move |pos| {
+            trails
+                .neighbours4(pos)
+                .filter(move |&next| trails[next] == n)
+        })
+    }
+
+    fn find_trails(&self, trailsystem: &TrailSystem) -> u32 {
+        let trails = &trailsystem.trails;
+        trailsystem
+            .trailheads
+            .iter()
+            .map(|&start| {
+                let mut to_visit = HashSet::from([start]);
+                for n in 1..=9 {
+                    to_visit = Self::climb(trails, to_visit, n).collect();
+                }
+                to_visit.len() as u32
+            })
+            .sum()
+    }
+
+    fn find_trails_distinct(&self, trailsystem: &TrailSystem) -> u32 {
+        let trails = &trailsystem.trails;
+        trailsystem
+            .trailheads
+            .iter()
+            .map(|&start| {
+                let mut to_visit = vec![start];
+                for n in 1..=9 {
+                    to_visit = Self::climb(trails, to_visit, n).collect();
+                }
+                to_visit.len() as u32
+            })
+            .sum()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let trailsystem = read_trails(input);
+    let hiker = Hiker {};
+    let valid_trail_count = hiker.find_trails(&trailsystem);
+    Some(valid_trail_count)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let trailsystem = read_trails(input);
+    let hiker = Hiker {};
+    let valid_trail_count = hiker.find_trails_distinct(&trailsystem);
+    Some(valid_trail_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(36));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(81));
+    }
+}
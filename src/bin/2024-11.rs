@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use memoize::memoize;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11);
 
 // This was for part 1, but count_stones works better
 #[allow(unused)]
@@ -84,13 +84,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         // No examples for 75 blinks, double check the new algo works for 25
         assert_eq!(result, Some(55312));
     }
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use advent_of_code::grid::{Grid, NEIGHBOURS_4};
+use advent_of_code::paths::regions;
+use advent_of_code::template::Solution;
+use tracing::instrument;
+
+advent_of_code::solution!(2024, 12, Day12);
+
+struct Day12;
+
+#[instrument(skip_all)]
+fn process_grid(grid: &Grid<char>) -> Vec<HashSet<(i32, i32)>> {
+    regions(grid)
+        .into_iter()
+        .map(|region| region.into_iter().map(|pos| (pos.x, pos.y)).collect())
+        .collect()
+}
+
+fn calculate_cost(regions: Vec<HashSet<(i32, i32)>>) -> i32 {
+    regions
+        .iter()
+        .map(|region| region.len() as i32 * perimeter(&region))
+        .sum()
+}
+
+fn calculate_cost_part2(regions: Vec<HashSet<(i32, i32)>>) -> i32 {
+    regions
+        .iter()
+        .map(|region| region.len() as i32 * number_sides(&region))
+        .sum()
+}
+
+/// insight: the number of sides a shape has is equal to the number of corners
+/// we just have to figure out how to count corners...
+fn number_sides(region: &HashSet<(i32, i32)>) -> i32 {
+    region.iter().fold(0i32, |mut acc, (x, y)| {
+        // TODO figure out a better way to do this, iterate rotating 90deg?
+        // brute is good enough for now
+        let up = region.contains(&(*x, y - 1));
+        let up_right = region.contains(&(x + 1, y - 1));
+        let right = region.contains(&(x + 1, *y));
+        let down_right = region.contains(&(x + 1, y + 1));
+        let down = region.contains(&(*x, y + 1));
+        let down_left = region.contains(&(x - 1, y + 1));
+        let left = region.contains(&(x - 1, *y));
+        let up_left = region.contains(&(x - 1, y - 1));
+
+        if up && right && !up_right {
+            // inside corner
+            acc += 1;
+        } else if !up && !right {
+            // outside corner
+            acc += 1;
+        }
+
+        if right && down && !down_right {
+            acc += 1;
+        } else if !right && !down {
+            acc += 1;
+        }
+
+        if down && left && !down_left {
+            acc += 1;
+        } else if !down && !left {
+            acc += 1;
+        }
+
+        if left && up && !up_left {
+            acc += 1;
+        } else if !left && !up {
+            acc += 1;
+        }
+
+        acc
+    })
+}
+
+fn perimeter(region: &HashSet<(i32, i32)>) -> i32 {
+    region.iter().fold(0i32, |mut acc, pos| {
+        acc += 4;
+        for d in NEIGHBOURS_4 {
+            if region.contains(&((pos.0 + d.x, pos.1 + d.y))) {
+                acc -= 1;
+            }
+        }
+        acc
+    })
+}
+
+impl Solution for Day12 {
+    type Parsed<'a> = Grid<char>;
+    type PartOne = Option<i32>;
+    type PartTwo = Option<i32>;
+
+    fn parse(input: &str) -> miette::Result<Self::Parsed<'_>> {
+        Ok(Grid::parse(input, Some)?)
+    }
+
+    #[instrument(skip_all)]
+    fn part_one(grid: &Grid<char>) -> Self::PartOne {
+        let regions = process_grid(grid);
+
+        let cost = calculate_cost(regions);
+        Some(cost)
+    }
+
+    fn part_two(grid: &Grid<char>) -> Self::PartTwo {
+        let regions = process_grid(grid);
+
+        let cost = calculate_cost_part2(regions);
+        Some(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    advent_of_code::example_tests!(default, small);
+}
@@ -3,7 +3,7 @@ use nalgebra::{Matrix2, Vector2};
 use nalgebra_glm::determinant;
 use regex::Regex;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2024, 13);
 
 #[derive(Debug, Default)]
 struct ClawMachine {
@@ -126,13 +126,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         // Ugh, no new test for part two
         assert_eq!(result, None);
     }
@@ -1,9 +1,15 @@
-use bmp::Image;
+use advent_of_code::ints::ints;
+use advent_of_code::visualize::{self, colors, Image};
 use glam::IVec2;
-use regex::Regex;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2024, 14);
+
+advent_of_code::params! {
+    /// Size of the area the robots patrol, the examples use 11x7.
+    width: i32 = 101,
+    height: i32 = 103,
+}
 
 #[derive(Debug)]
 pub struct Robot {
@@ -27,54 +33,33 @@ impl Robot {
 
 fn parse_input(input: &str) -> Vec<Robot> {
     // p=0,4 v=3,-3
-    let re = Regex::new(r"p=(?<px>[-\d]+),(?<py>[-\d]+) v=(?<vx>[-\d]+),(?<vy>[-\d]+)")
-        .expect("invalid regex");
     input
         .lines()
         .map(|line| {
-            let cap = re.captures(line).expect("didn't match {line}");
+            let [px, py, vx, vy] = ints(line).unwrap_or_else(|e| panic!("{e}"));
             Robot {
-                pos: IVec2 {
-                    x: cap["px"].parse().unwrap(),
-                    y: cap["py"].parse().unwrap(),
-                },
-                velocity: IVec2 {
-                    x: cap["vx"].parse().unwrap(),
-                    y: cap["vy"].parse().unwrap(),
-                },
+                pos: IVec2 { x: px, y: py },
+                velocity: IVec2 { x: vx, y: vy },
             }
         })
         .collect()
 }
 
-fn display(n: u32, robots: &Vec<Robot>, _gridlen: &IVec2) {
-    if n != 6752 {
-        // now that I know the answer, I'm not generating 8k BMP files...
+fn display(n: u32, robots: &[Robot], gridlen: &IVec2) {
+    if !visualize::is_enabled() {
         return;
     }
-    let mut img = Image::new(101, 103);
-
-    for (x, y) in img.coordinates() {
-        img.set_pixel(x, y, bmp::consts::BLACK);
-    }
-    robots.iter().for_each(|r| {
-        img.set_pixel(
-            r.pos.x.try_into().unwrap(),
-            r.pos.y.try_into().unwrap(),
-            bmp::consts::GREEN,
-        );
-    });
-    let _ = img.save(format!("data/images/iter-{:04}.bmp", n));
+    let mut img = Image::new(*gridlen, colors::BLACK);
+    robots.iter().for_each(|r| img.set(r.pos, colors::GREEN));
+    visualize::show(PUZZLE, &format!("iter-{n:04}"), &img);
 }
 
 // seems like we can probably simulate for part 1, guessing we'd have to figure out calculation for part 2 esp. since they're giving us grid size...
 pub fn part_one(input: &str) -> Option<u32> {
     let mut robots = parse_input(input);
 
-    let mut gridlen = IVec2::new(101, 103);
-    if cfg!(test) {
-        gridlen = IVec2::new(11, 7);
-    }
+    let params = params();
+    let gridlen = IVec2::new(params.width, params.height);
 
     let x_mid: i32 = (gridlen.x / 2).try_into().unwrap();
     let y_mid: i32 = (gridlen.y / 2).try_into().unwrap();
@@ -111,18 +96,24 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let mut robots = parse_input(input);
 
-    let gridlen = IVec2::new(101, 103);
+    let params = params();
+    let gridlen = IVec2::new(params.width, params.height);
 
-    for n in 1..7000 {
+    // yes, I REALLY generated 7,000 images and previewed them in the filesystem to find the !@#$!@ Xmas tree,
+    // turns out it's the first frame where no two robots overlap. positions repeat after width * height ticks.
+    for n in 1..=(gridlen.x * gridlen.y) as u32 {
         robots.iter_mut().for_each(|r| {
             r.patrol(1, &gridlen);
         });
 
-        // yes, I REALLY generate 7,000 BMP files and preview them in the filesystem to find the !@#$!@ Xmas tree
-        display(n, &robots, &gridlen);
+        let positions: HashSet<IVec2> = robots.iter().map(|r| r.pos).collect();
+        if positions.len() == robots.len() {
+            display(n, &robots, &gridlen);
+            return Some(n);
+        }
     }
 
-    Some(6752)
+    None
 }
 
 #[cfg(test)]
@@ -157,15 +148,11 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
-    }
+    advent_of_code::example_tests!(default);
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -1,12 +1,9 @@
-use bmp::{
-    consts::{BLACK, BLUE, LIME_GREEN, ORANGE, ORANGE_RED, RED, WHITE_SMOKE},
-    Image,
-};
+use advent_of_code::visualize::{self, colors, Image};
 use glam::IVec2;
 use itertools::Itertools;
 use std::collections::VecDeque;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2024, 15);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Contents {
@@ -101,31 +98,31 @@ impl Grid {
     }
 
     fn create_image(&self, img_name: &str) {
-        let mut img: Image = Image::new(self.dim.x as u32, self.dim.y as u32);
-
-        for (x, y) in img.coordinates() {
-            img.set_pixel(x, y, BLACK);
+        if !visualize::is_enabled() {
+            return;
         }
+        let mut img = Image::new(self.dim, colors::BLACK);
 
         self.contents
             .iter()
             .enumerate()
             .filter(|(_, c)| **c != Contents::EmptySpace)
             .for_each(|(idx, c)| {
-                let y = (idx / self.dim.x as usize) as u32;
-                let x = (idx % self.dim.x as usize) as u32;
+                let y = (idx / self.dim.x as usize) as i32;
+                let x = (idx % self.dim.x as usize) as i32;
 
-                match c {
-                    Contents::Wall => img.set_pixel(x, y, WHITE_SMOKE),
-                    Contents::Robot => img.set_pixel(x, y, LIME_GREEN),
-                    Contents::Box => img.set_pixel(x, y, BLUE),
-                    Contents::LeftBox => img.set_pixel(x, y, ORANGE),
-                    Contents::RightBox => img.set_pixel(x, y, ORANGE_RED),
+                let color = match c {
+                    Contents::Wall => colors::WHITE,
+                    Contents::Robot => colors::GREEN,
+                    Contents::Box => colors::BLUE,
+                    Contents::LeftBox => colors::ORANGE,
+                    Contents::RightBox => colors::RED,
                     _ => unreachable!(),
-                }
+                };
+                img.set(IVec2::new(x, y), color);
             });
 
-        let _ = img.save(format!("data/images/15-{img_name}.bmp"));
+        visualize::show(PUZZLE, img_name, &img);
     }
 
     fn gps_score(&self) -> u32 {
@@ -253,13 +250,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -0,0 +1,86 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::paths::{dijkstra_turns, shortest_path_tiles, TurnCosts};
+use advent_of_code::template::InputError;
+use glam::IVec2;
+
+advent_of_code::solution!(2024, 16);
+
+/// Moving forward costs a point, turning costs 1000.
+const COSTS: TurnCosts = TurnCosts {
+    step: 1,
+    turn: 1000,
+};
+
+#[derive(Debug)]
+struct Maze {
+    start: IVec2,
+    end: IVec2,
+    grid: Grid<char>,
+}
+
+impl Maze {
+    fn is_open(&self, pos: IVec2) -> bool {
+        self.grid[pos] != '#'
+    }
+}
+
+fn parse_grid(input: &str) -> Result<Maze, InputError> {
+    let is_symbol = |c| matches!(c, '#' | '.' | 'S' | 'E');
+
+    // NOTE: point at an unexpected symbol even if its row is ragged, `Grid::parse` checks the width of a row first.
+    if let Some(symbol) = input
+        .matches(|c: char| !is_symbol(c) && !c.is_whitespace())
+        .next()
+    {
+        let message = format!("unexpected symbol `{symbol}` in the grid");
+        return Err(InputError::at(input, symbol, message));
+    }
+
+    let grid = Grid::parse(input, |c| is_symbol(c).then_some(c))?;
+    let find = |symbol| {
+        grid.find(|&c| c == symbol)
+            .ok_or_else(|| InputError::new(format!("no `{symbol}` in the grid"), (0, 0)))
+    };
+
+    Ok(Maze {
+        start: find('S')?,
+        end: find('E')?,
+        grid,
+    })
+}
+
+#[tracing::instrument(skip(input))]
+pub fn part_one(input: &str) -> Result<u32, InputError> {
+    let maze = parse_grid(input)?;
+    let costs = dijkstra_turns(&maze.grid, [(maze.start, IVec2::X)], COSTS, |_, to| {
+        maze.is_open(to)
+    });
+    Ok(costs.min_at(maze.end).expect("some path found"))
+}
+
+#[tracing::instrument(skip(input))]
+pub fn part_two(input: &str) -> Result<u32, InputError> {
+    let maze = parse_grid(input)?;
+    let (_cost, spots_to_sit) = shortest_path_tiles(
+        &maze.grid,
+        (maze.start, IVec2::X),
+        maze.end,
+        COSTS,
+        |_, to| maze.is_open(to),
+    )
+    .expect("some path found");
+    Ok(spots_to_sit.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::example_tests!(default, small);
+
+    #[test]
+    fn test_unexpected_symbol() {
+        let result = part_one("#S.\n#x#E\n");
+        assert_eq!(result.unwrap_err().offset(), 5);
+    }
+}
@@ -1,49 +1,47 @@
-use itertools::Itertools;
+use advent_of_code::template::InputError;
 use tracing::{debug, info, instrument};
 
-advent_of_code::solution!(17);
-
-fn parse_input(input: &str) -> Machine {
-    let [a, b, c] = input
-        .lines()
-        .take(3)
-        .map(|line| {
-            line.split_once(":")
-                .unwrap()
-                .1
-                .to_string()
-                .trim()
-                .parse::<u32>()
-                .expect("number")
-        })
-        .collect_vec()[0..3]
-    else {
-        unreachable!("invalid")
-    };
-    let instructions = input
-        .lines()
-        .skip(4) // 3 registers + newline
-        .next()
-        .expect("program")
-        .trim_end()
+advent_of_code::solution!(2024, 17);
+
+fn parse_input(input: &str) -> Result<Machine, InputError> {
+    let mut lines = input.lines();
+    let mut registers = [0u32; 3];
+    for (register, name) in registers.iter_mut().zip(["A", "B", "C"]) {
+        let line = lines
+            .next()
+            .ok_or_else(|| InputError::new(format!("missing register {name}"), (input.len(), 0)))?;
+        let (_, value) = line
+            .split_once(":")
+            .ok_or_else(|| InputError::at(input, line, "expected `Register <name>: <value>`"))?;
+        let value = value.trim();
+        *register = value
+            .parse()
+            .map_err(|_| InputError::at(input, value, "expected a number"))?;
+    }
+    let [a, b, c] = registers;
+
+    let program = lines
+        .nth(1) // skip the newline after the registers
+        .ok_or_else(|| InputError::new("missing program", (input.len(), 0)))?;
+    let (_, codes) = program
         .split_once(":")
-        .expect("instructions")
-        .1
+        .ok_or_else(|| InputError::at(input, program, "expected `Program: <instructions>`"))?;
+    let instructions = codes
         .trim()
         .split(",")
         .map(|c| match c {
-            "0" => Instruction::ADV,
-            "1" => Instruction::BXL,
-            "2" => Instruction::BST,
-            "3" => Instruction::JNZ,
-            "4" => Instruction::BXC,
-            "5" => Instruction::OUT,
-            "6" => Instruction::BDV,
-            "7" => Instruction::CDV,
-            _ => unreachable!("invalid instruction code"),
+            "0" => Ok(Instruction::ADV),
+            "1" => Ok(Instruction::BXL),
+            "2" => Ok(Instruction::BST),
+            "3" => Ok(Instruction::JNZ),
+            "4" => Ok(Instruction::BXC),
+            "5" => Ok(Instruction::OUT),
+            "6" => Ok(Instruction::BDV),
+            "7" => Ok(Instruction::CDV),
+            _ => Err(InputError::at(input, c, "invalid instruction code")),
         })
-        .collect_vec();
-    Machine::new(a, b, c, instructions.to_vec())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Machine::new(a, b, c, instructions))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -157,12 +155,12 @@ impl Machine {
 }
 
 #[instrument(skip(input))]
-pub fn part_one(input: &str) -> Option<String> {
-    let mut machine = parse_input(input);
+pub fn part_one(input: &str) -> Result<String, InputError> {
+    let mut machine = parse_input(input)?;
     info!(?machine);
     machine.run();
     info!(ending_state =  ?machine);
-    Some(machine.output.join(","))
+    Ok(machine.output.join(","))
 }
 
 #[instrument(skip(input))]
@@ -222,18 +220,18 @@ Program: 1,7",
     // looks like this one will be really annoying... time to learn how to use rstest
     fn test_examples(#[case] input: &str, #[case] expected: String) {
         let result = part_one(input);
-        assert_eq!(result, Some(expected));
+        assert_eq!(result.unwrap(), expected);
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result.ok(), None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -1,9 +1,17 @@
+use advent_of_code::parsers::{ivec2, lines, parse_all};
 use glam::IVec2;
 use itertools::Itertools;
 use pathfinding::prelude::astar;
 use tracing::{info, instrument};
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2024, 18);
+
+advent_of_code::params! {
+    /// Largest coordinate of the memory space, the examples use 6.
+    dim: usize = 70,
+    /// Number of bytes that have fallen for part one, the examples use 12.
+    bytes: usize = 1024,
+}
 
 #[derive(Debug)]
 struct Grid {
@@ -14,19 +22,9 @@ struct Grid {
 }
 
 fn parse_input(input: &str) -> Grid {
-    let walls = input
-        .lines()
-        .map(|line| {
-            let (x, y) = line
-                .split_once(",")
-                .expect("should have been comma separated points");
-            IVec2::new(
-                x.parse().expect("x was invalid"),
-                y.parse().expect("y was invalid"),
-            )
-        })
-        .collect();
-    let dim = if cfg!(test) { 6 } else { 70 };
+    let walls =
+        parse_all(input, lines(ivec2(","))).expect("should have been comma separated points");
+    let dim = params().dim;
     Grid {
         dim,
         start: IVec2::ZERO,
@@ -39,7 +37,7 @@ const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
 
 #[instrument(skip(input))]
 pub fn part_one(input: &str) -> Option<u32> {
-    let elapsed = if cfg!(test) { 12 } else { 1024 };
+    let elapsed = params().bytes;
     let grid = parse_input(input);
     // tracing::debug!("{:?}", &grid.walls[..elapsed]);
     let solution = astar(
@@ -69,9 +67,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 // yeah, I don't feel like figuring this out right now... just brute force (binary search basically)
 #[instrument(skip(input))]
 pub fn part_two(input: &str) -> Option<String> {
-    let known_good = if cfg!(test) { 12 } else { 1024 }; // we know there's a path at least through these because of part 1
-    let input_len = if cfg!(test) { 25 } else { 3450 }; // length of the inputs
+    let known_good = params().bytes; // we know there's a path at least through these because of part 1
     let grid = parse_input(input);
+    let input_len = grid.walls.len();
     // tracing::debug!("{:?}", &grid.walls[..elapsed]);
 
     let first_blocked_idx = (known_good..=input_len)
@@ -112,21 +110,5 @@ pub fn part_two(input: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use tracing_test::traced_test;
-
-    use super::*;
-
-    #[traced_test]
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(22));
-    }
-
-    #[traced_test]
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_owned()));
-    }
+    advent_of_code::example_tests!(default);
 }
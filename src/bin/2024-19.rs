@@ -2,7 +2,7 @@ use itertools::Itertools;
 use memoize::memoize;
 use tracing::instrument;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19);
 
 #[derive(Debug)]
 struct Problem {
@@ -81,13 +81,13 @@ mod tests {
     #[traced_test]
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(16));
     }
 }
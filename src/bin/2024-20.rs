@@ -1,11 +1,17 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::InputError;
 use glam::IVec2;
 use itertools::Itertools;
 use pathfinding::prelude::astar;
 use tracing::{debug, instrument};
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2024, 20);
+
+advent_of_code::params! {
+    /// Minimum number of picoseconds a cheat has to save, the examples use 2.
+    savings: u32 = 100,
+}
 
 #[derive(Debug)]
 struct Grid {
@@ -28,37 +34,36 @@ impl Grid {
 
 const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_Y, IVec2::Y, IVec2::NEG_X];
 
-fn parse_grid(input: &str) -> Grid {
-    input
-        .lines()
-        .enumerate()
-        .fold(Grid::new(), |mut acc, (y, line)| {
-            if acc.dim.y < y as i32 {
-                acc.dim.y = y as i32;
-            }
-            line.chars().enumerate().fold(acc, |mut acc, (x, ch)| {
-                let pos = IVec2::new(x as i32, y as i32);
-                if acc.dim.x < x as i32 {
-                    acc.dim.x = x as i32;
+fn parse_grid(input: &str) -> Result<Grid, InputError> {
+    let mut grid = Grid::new();
+    for (y, line) in input.lines().enumerate() {
+        grid.dim.y = grid.dim.y.max(y as i32);
+        for (x, ch) in line.char_indices() {
+            let pos = IVec2::new(x as i32, y as i32);
+            grid.dim.x = grid.dim.x.max(x as i32);
+            match ch {
+                '#' => {
+                    grid.walls.insert(pos);
                 }
-                match ch {
-                    '#' => {
-                        acc.walls.insert(pos);
-                        ()
-                    }
-                    'S' => acc.start = pos,
-                    'E' => acc.end = pos,
-                    '.' => (),
-                    _ => unreachable!("shouldn't be any other symbols in the grid"),
-                };
-                acc
-            })
-        })
+                'S' => grid.start = pos,
+                'E' => grid.end = pos,
+                '.' => (),
+                _ => {
+                    return Err(InputError::at(
+                        input,
+                        &line[x..x + ch.len_utf8()],
+                        format!("unexpected symbol `{ch}` in the grid"),
+                    ))
+                }
+            };
+        }
+    }
+    Ok(grid)
 }
 
 #[instrument(skip(input))]
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = parse_grid(input);
+pub fn part_one(input: &str) -> Result<u32, InputError> {
+    let grid = parse_grid(input)?;
     debug!(?grid);
     let (_path, no_cheat_cost) = astar(
         &grid.start,
@@ -92,7 +97,7 @@ pub fn part_one(input: &str) -> Option<u32> {
             DIRECTIONS.iter().map(|d| *p + d).any(|new_pos| !grid.walls.contains(&new_pos)))
         .collect_vec();
 
-    let target_savings = if cfg!(test) { 2 } else { 100 };
+    let target_savings = params().savings;
 
     let savings = eligible_walls
         .iter()
@@ -121,7 +126,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .filter(|savings| *savings >= target_savings)
         .count();
 
-    Some(savings as u32)
+    Ok(savings as u32)
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
@@ -133,15 +138,11 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(44));
-    }
+    advent_of_code::example_tests!(default);
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -5,7 +5,7 @@ use memoize::memoize;
 use pathfinding::prelude::astar;
 use tracing::instrument;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(2024, 21);
 
 /// yeah, this is probably not the best either...  maybe building up reachable paths from each char would be easier
 #[memoize]
@@ -300,13 +300,13 @@ mod tests {
     #[traced_test]
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(126384));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -3,7 +3,7 @@ use std::collections::{BTreeMap, HashSet};
 use itertools::Itertools;
 use tracing::{debug, info, instrument};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(2024, 23);
 
 fn find_triads(graph: &BTreeMap<String, HashSet<String>>) -> Vec<(String, String, String)> {
     let mut results = vec![];
@@ -81,13 +81,13 @@ mod tests {
     #[traced_test]
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(7));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
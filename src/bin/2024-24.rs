@@ -1,15 +1,16 @@
-use std::{
-    collections::{BTreeMap, VecDeque},
-    fs,
-};
+use std::collections::{BTreeMap, VecDeque};
 
+use advent_of_code::template::Solution;
+use advent_of_code::visualize;
 use derive_more::derive::Display;
 use itertools::Itertools;
 use petgraph::{dot::Dot, Graph};
 use std::fmt::Display;
 use tracing::debug;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(2024, 24, Day24);
+
+struct Day24;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
 enum Logic {
@@ -100,8 +101,8 @@ fn simulate(wires: &BTreeMap<&str, bool>, gates: &Vec<Gate>) -> u64 {
     output
 }
 
-/// turn into a graph and build a graphviz file
-pub fn visualize(wires: BTreeMap<&str, bool>, gates: Vec<Gate>) -> Graph<String, String> {
+/// turn into a graph and build a graphviz file, written with `--visualize`
+pub fn visualize(wires: &BTreeMap<&str, bool>, gates: &[Gate]) -> Graph<String, String> {
     let mut graph = Graph::<String, String>::new();
     let mut nodemap = BTreeMap::new();
 
@@ -120,21 +121,31 @@ pub fn visualize(wires: BTreeMap<&str, bool>, gates: Vec<Gate>) -> Graph<String,
         graph.extend_with_edges(&[(a, op_node), (b, op_node), (op_node, out)]);
     }
     let data = format!("{:?}", Dot::new(&graph));
-    fs::write("data/images/day-24.dot", data).expect("Unable to write file");
+    visualize::show_file(PUZZLE, "graph", "dot", &data);
     graph
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (wires, gates) = parse_input(input);
-    let result = simulate(&wires, &gates);
-    // println!("{:?}", result);
-    Some(result)
-}
+impl Solution for Day24 {
+    type Parsed<'a> = (BTreeMap<&'a str, bool>, Vec<Gate>);
+    type PartOne = Option<u64>;
+    type PartTwo = Option<u32>;
+
+    fn parse(input: &str) -> miette::Result<Self::Parsed<'_>> {
+        Ok(parse_input(input))
+    }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (wires, gates) = parse_input(input);
-    visualize(wires, gates);
-    None
+    fn part_one((wires, gates): &Self::Parsed<'_>) -> Self::PartOne {
+        let result = simulate(wires, gates);
+        // println!("{:?}", result);
+        Some(result)
+    }
+
+    fn part_two((wires, gates): &Self::Parsed<'_>) -> Self::PartTwo {
+        if visualize::is_enabled() {
+            visualize(wires, gates);
+        }
+        None
+    }
 }
 
 #[cfg(test)]
@@ -146,14 +157,15 @@ mod tests {
     #[traced_test]
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = Day24::part_one(&Day24::parse(&input).unwrap());
         assert_eq!(result, Some(4));
     }
 
     #[traced_test]
     #[test]
     fn test_part_two() {
-        let result = part_two(
+        let parsed = Day24::parse(
             r"x00: 0
 x01: 1
 x02: 0
@@ -173,7 +185,9 @@ x02 AND y02 -> z01
 x03 AND y03 -> z03
 x04 AND y04 -> z04
 x05 AND y05 -> z00",
-        );
+        )
+        .unwrap();
+        let result = Day24::part_two(&parsed);
         assert_eq!(result, None);
     }
 }
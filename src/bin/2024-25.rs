@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2024, 25);
 
 #[derive(Debug, PartialEq)]
 enum DeviceType {
@@ -110,13 +110,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -0,0 +1,377 @@
+//! A rectangular grid of cells, the shape of most puzzle inputs.
+//!
+//! Positions are [`IVec2`]s with `x` as column and `y` as row, `(0, 0)` is the top left cell.
+//! Positions outside of the grid are valid arguments everywhere and simply have no cell.
+//!
+//! ```
+//! # use advent_of_code::grid::{Grid, NEIGHBOURS_4};
+//! # use glam::IVec2;
+//! let grid = Grid::parse("#.S\n#..", |c| Some(c)).unwrap();
+//! let start = grid.find(|&c| c == 'S').unwrap();
+//! assert_eq!(start, IVec2::new(2, 0));
+//! assert_eq!(grid.neighbours4(start).filter(|&p| grid[p] == '.').count(), 2);
+//! assert_eq!(grid.to_string(), "#.S\n#..");
+//! ```
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use glam::{IVec2, Vec2Swizzles};
+
+use crate::template::InputError;
+
+/// Up, right, down and left, in clockwise order.
+pub const NEIGHBOURS_4: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// All eight neighbours, in clockwise order starting with up.
+pub const NEIGHBOURS_8: [IVec2; 8] = [
+    IVec2::NEG_Y,
+    IVec2::new(1, -1),
+    IVec2::X,
+    IVec2::ONE,
+    IVec2::Y,
+    IVec2::new(-1, 1),
+    IVec2::NEG_X,
+    IVec2::NEG_ONE,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    size: IVec2,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid from text, one row per line. `map` turns a character into a cell, `None` rejects it.
+    ///
+    /// All rows must have the same width, errors point at the offending row or character.
+    pub fn parse(input: &str, mut map: impl FnMut(char) -> Option<T>) -> Result<Self, InputError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in input.lines() {
+            if line.chars().count() != width {
+                let message = format!("expected a row of width {width}");
+                return Err(InputError::at(input, line, message));
+            }
+
+            for (i, c) in line.char_indices() {
+                match map(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let message = format!("unexpected symbol `{c}` in the grid");
+                        return Err(InputError::at(input, &line[i..i + c.len_utf8()], message));
+                    }
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            size: IVec2::new(width as i32, height),
+            cells,
+        })
+    }
+
+    /// A grid of the given size from cells in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match the size.
+    pub fn from_cells(size: IVec2, cells: Vec<T>) -> Self {
+        assert!(size.cmpge(IVec2::ZERO).all(), "negative grid size {size}");
+        assert_eq!(
+            cells.len(),
+            (size.x * size.y) as usize,
+            "grid of size {size} needs {} cells",
+            size.x * size.y
+        );
+        Self { size, cells }
+    }
+
+    /// A grid of the given size with every cell set to `value`.
+    pub fn filled(size: IVec2, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_cells(size, vec![value; (size.x * size.y).max(0) as usize])
+    }
+
+    /// Width and height of the grid.
+    pub fn size(&self) -> IVec2 {
+        self.size
+    }
+
+    pub fn width(&self) -> i32 {
+        self.size.x
+    }
+
+    pub fn height(&self) -> i32 {
+        self.size.y
+    }
+
+    /// Whether the position is inside of the grid.
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.cmpge(IVec2::ZERO).all() && pos.cmplt(self.size).all()
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| (pos.y * self.size.x + pos.x) as usize)
+    }
+
+    fn pos_of(&self, index: usize) -> IVec2 {
+        let index = index as i32;
+        IVec2::new(index % self.size.x, index / self.size.x)
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        let size = self.size;
+        (0..size.y).flat_map(move |y| (0..size.x).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// All cells with their position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The cells of a row, from left to right.
+    ///
+    /// # Panics
+    /// Panics if the row is outside of the grid.
+    pub fn row(&self, y: i32) -> &[T] {
+        assert!(
+            (0..self.size.y).contains(&y),
+            "row {y} is outside of the grid"
+        );
+        let start = (y * self.size.x) as usize;
+        &self.cells[start..start + self.size.x as usize]
+    }
+
+    /// The cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column is outside of the grid.
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+        assert!(
+            (0..self.size.x).contains(&x),
+            "column {x} is outside of the grid"
+        );
+        (0..self.size.y).map(move |y| &self[IVec2::new(x, y)])
+    }
+
+    /// The in-bounds orthogonal neighbours of a position.
+    pub fn neighbours4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(|&next| self.contains(next))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of a position.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(|&next| self.contains(next))
+    }
+
+    /// Positions from `start` in steps of `dir` until the edge of the grid. `start` itself is not included.
+    ///
+    /// With a direction of [`NEIGHBOURS_8`] this walks along a row, a column or a diagonal.
+    pub fn ray(&self, start: IVec2, dir: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        std::iter::successors(Some(start), move |&pos| Some(pos + dir))
+            .skip(1)
+            .take_while(move |&pos| dir != IVec2::ZERO && self.contains(pos))
+    }
+
+    /// The position of the first cell in row-major order that matches the predicate.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.pos_of(index))
+    }
+
+    /// The positions of all cells that match the predicate, in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            size: self.size,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.size.yx(), |pos| pos.yx())
+    }
+
+    /// Rotate by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let size = self.size;
+        self.remap(size.yx(), |pos| IVec2::new(pos.y, size.y - 1 - pos.x))
+    }
+
+    /// Rotate by 90 degrees counter-clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let size = self.size;
+        self.remap(size.yx(), |pos| IVec2::new(size.x - 1 - pos.y, pos.x))
+    }
+
+    /// A grid of `size` whose cell at `pos` is the cell of this grid at `source(pos)`.
+    fn remap(&self, size: IVec2, source: impl Fn(IVec2) -> IVec2) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = Grid {
+            size,
+            cells: Vec::with_capacity(self.cells.len()),
+        };
+        let cells = grid.positions().map(|pos| self[source(pos)].clone());
+        grid.cells = cells.collect();
+        grid
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+/// Rows of cells, one per line, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.size.y {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::Grid;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = Grid::parse("12\n34\n56\n", |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.size(), IVec2::new(2, 3));
+        assert_eq!(grid[IVec2::new(1, 2)], 6);
+        assert_eq!(grid.get(IVec2::new(2, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "12\n34\n56");
+    }
+
+    #[test]
+    fn rejects_malformed_grids() {
+        let err = Grid::parse("..\n.\n", Some).unwrap_err();
+        assert_eq!(err.offset(), 3);
+        assert_eq!(err.to_string(), "expected a row of width 2");
+
+        let err = Grid::parse("..\n.x", |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!(err.offset(), 4);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = chars("abc\ndef\nghi");
+
+        let corner: Vec<_> = grid.neighbours4(IVec2::ZERO).map(|p| grid[p]).collect();
+        assert_eq!(corner, ['b', 'd']);
+
+        let center: String = grid.neighbours8(IVec2::ONE).map(|p| grid[p]).collect();
+        assert_eq!(center, "bcfihgda");
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = chars("abc\ndef\nghi");
+
+        let ray: String = grid.ray(IVec2::ZERO, IVec2::ONE).map(|p| grid[p]).collect();
+        assert_eq!(ray, "ei");
+
+        let ray: String = grid
+            .ray(IVec2::new(2, 1), IVec2::NEG_X)
+            .map(|p| grid[p])
+            .collect();
+        assert_eq!(ray, "ed");
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cfi");
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = chars("#.S\n.#.");
+
+        assert_eq!(grid.find(|&c| c == 'S'), Some(IVec2::new(2, 0)));
+        assert_eq!(grid.find(|&c| c == 'E'), None);
+        assert_eq!(
+            grid.find_all(|&c| c == '#').collect::<Vec<_>>(),
+            [IVec2::ZERO, IVec2::ONE]
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -0,0 +1,179 @@
+//! Integer extraction and whitespace tokenizing for lines of puzzle input.
+//!
+//! Integers are runs of ASCII digits, everything in between is skipped. A `-` directly in front of the digits is a sign
+//! for signed types only, so ranges like `3-5` are best read as unsigned.
+//!
+//! ```
+//! # use advent_of_code::ints::{all_ints, ints};
+//! let [px, py, vx, vy] = ints::<i64, 4>("p=0,4 v=3,-3").unwrap();
+//! assert_eq!((px, py, vx, vy), (0, 4, 3, -3));
+//!
+//! assert_eq!(all_ints::<u32>("3-5 and 7").unwrap(), [3, 5, 7]);
+//! assert!(ints::<u8, 2>("1 2 3").is_err());
+//! ```
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use miette::Diagnostic;
+
+use crate::template::InputError;
+
+/// Integer types that can be extracted.
+pub trait Integer: FromStr {
+    /// Whether a leading `-` belongs to the number.
+    const SIGNED: bool;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr => $($ty:ty),*) => {
+        $( impl Integer for $ty { const SIGNED: bool = $signed; } )*
+    };
+}
+
+impl_integer!(true => i8, i16, i32, i64, i128, isize);
+impl_integer!(false => u8, u16, u32, u64, u128, usize);
+
+/// A line or block that does not contain the expected integers or fields.
+#[derive(Debug, Diagnostic)]
+pub struct ExtractError {
+    message: String,
+    /// Byte range of the offending part of the text.
+    span: Range<usize>,
+}
+
+impl ExtractError {
+    fn new(message: String, span: Range<usize>) -> Self {
+        Self { message, span }
+    }
+
+    /// Locate the error in the puzzle input, `text` must be the slice of `input` that was extracted from.
+    ///
+    /// # Panics
+    /// Panics if `text` is not a slice of `input`.
+    pub fn locate(&self, input: &str, text: &str) -> InputError {
+        InputError::at(input, &text[self.span.clone()], self.message.clone())
+    }
+}
+
+impl Error for ExtractError {}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Byte ranges of all integers in `text`.
+fn spans<T: Integer>(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+
+        let mut start = i;
+        if T::SIGNED && start > 0 && bytes[start - 1] == b'-' {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(start..i)
+    })
+}
+
+/// All integers in `text`, e.g. a line or the whole input.
+pub fn all_ints<T: Integer>(text: &str) -> Result<Vec<T>, ExtractError> {
+    spans::<T>(text)
+        .map(|span| {
+            let number = &text[span.clone()];
+            number.parse().map_err(|_| {
+                let message = format!("`{number}` is not a valid `{}`", type_name::<T>());
+                ExtractError::new(message, span)
+            })
+        })
+        .collect()
+}
+
+/// Exactly `N` integers, to destructure a line: `let [a, b] = ints::<i64, 2>(line)?`.
+pub fn ints<T: Integer, const N: usize>(text: &str) -> Result<[T; N], ExtractError> {
+    let numbers = all_ints(text)?;
+    let found = numbers.len();
+    numbers.try_into().map_err(|_| {
+        let message = format!("expected {N} integers, found {found} in `{text}`");
+        ExtractError::new(message, 0..text.len())
+    })
+}
+
+/// The integers of every line.
+pub fn ints_per_line<T: Integer>(text: &str) -> Result<Vec<Vec<T>>, ExtractError> {
+    text.lines()
+        .map(|line| {
+            let offset = line.as_ptr() as usize - text.as_ptr() as usize;
+            all_ints(line).map_err(|e| {
+                let span = e.span.start + offset..e.span.end + offset;
+                ExtractError::new(e.message, span)
+            })
+        })
+        .collect()
+}
+
+/// Exactly `N` fields separated by whitespace: `let [name, _, value] = fields(line)?`.
+pub fn fields<const N: usize>(line: &str) -> Result<[&str; N], ExtractError> {
+    let fields: Vec<_> = line.split_ascii_whitespace().collect();
+    let found = fields.len();
+    fields.try_into().map_err(|_| {
+        let message = format!("expected {N} fields, found {found} in `{line}`");
+        ExtractError::new(message, 0..line.len())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{all_ints, fields, ints, ints_per_line};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(ints::<i32, 4>("p=0,4 v=3,-3").unwrap(), [0, 4, 3, -3]);
+        assert_eq!(ints::<u64, 2>("Button A: X+94, Y+34").unwrap(), [94, 34]);
+        assert_eq!(all_ints::<u32>("1-3 a: -7").unwrap(), [1, 3, 7]);
+        assert_eq!(all_ints::<i64>("1 -3\n-7").unwrap(), [1, -3, -7]);
+        assert_eq!(all_ints::<u8>("none").unwrap(), []);
+        assert_eq!(
+            ints_per_line::<u32>("190: 10 19\n83: 17 5").unwrap(),
+            [vec![190, 10, 19], vec![83, 17, 5]]
+        );
+    }
+
+    #[test]
+    fn reports_unexpected_counts() {
+        let err = ints::<i64, 2>("3 4 5").unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 integers, found 3 in `3 4 5`");
+
+        let err = fields::<2>("a b c").unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 fields, found 3 in `a b c`");
+        assert_eq!(fields::<3>("a  b\tc").unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn locates_invalid_integers() {
+        let input = "1 2\n3 300\n";
+        let err = ints_per_line::<u8>(input).unwrap_err();
+        assert_eq!(err.to_string(), "`300` is not a valid `u8`");
+        assert_eq!(err.locate(input, input).offset(), 6);
+
+        let line = input.lines().nth(1).unwrap();
+        let err = ints::<u8, 2>(line).unwrap_err();
+        assert_eq!(err.locate(input, line).offset(), 6);
+    }
+}
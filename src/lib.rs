@@ -1,3 +1,12 @@
+// NOTE: lets the scaffold templates, which use `advent_of_code::` paths, be built as part of the library tests.
+#[cfg(feature = "test_lib")]
+extern crate self as advent_of_code;
+
+pub mod grid;
+pub mod ints;
+pub mod parsers;
+pub mod paths;
 pub mod template;
+pub mod visualize;
 
 // Use this file to add helper functions and additional modules.
@@ -1,80 +1,266 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, extract, read, scaffold, solve, time, watch,
+};
+use advent_of_code::template::isolation;
 use args::{parse, AppArguments};
 
+// NOTE: generated by `build.rs`, links all solutions into this binary.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::time;
+    use advent_of_code::template::examples::DEFAULT_EXAMPLE;
+    use advent_of_code::template::isolation::{Limits, CHILD_COMMAND};
+    use advent_of_code::template::{Day, PuzzleId, Year};
+    use advent_of_code::visualize::Target;
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Extract {
+            puzzle: PuzzleId,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            examples: bool,
+            params: Vec<String>,
+            watch: bool,
+            part: Option<u8>,
+            input: Option<String>,
+            example: Option<String>,
+            visualize: Option<Target>,
         },
         All {
+            year: Year,
             release: bool,
+            store: bool,
+            jobs: Option<usize>,
+            limits: Limits,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            jobs: Option<usize>,
+            limits: Limits,
+            dhat: bool,
+        },
+        RunIsolated {
+            puzzle: PuzzleId,
+            time: bool,
+            memory_limit: Option<u64>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// `--parallel` runs days concurrently with one worker per CPU, `--jobs <n>` bounds the number of workers.
+    fn parse_jobs(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+        let parallel = args.contains("--parallel");
+        let jobs: Option<usize> = args.opt_value_from_str("--jobs")?;
+        Ok(jobs.or(parallel.then_some(0)))
+    }
+
+    /// `--timeout <seconds>` limits the time of every part, `--memory-limit <MiB>` the memory of every day.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        let memory: Option<u64> = args.opt_value_from_str("--memory-limit")?;
+
+        Ok(Limits {
+            timeout: timeout.map(Duration::from_secs_f64),
+            memory: memory.map(|mib| mib * 1024 * 1024),
+        })
+    }
+
+    /// `--year <year>` selects the year, otherwise `AOC_YEAR` is used.
+    fn parse_year(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Year>, Box<dyn std::error::Error>> {
+        Ok(args.opt_value_from_str("--year")?.or_else(Year::from_env))
+    }
+
+    /// Puzzles are given as `<year>-<day>` or as a day of the selected year.
+    fn parse_puzzle(
+        value: &str,
+        year: Option<Year>,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        match (value.parse::<Day>(), year) {
+            (Ok(day), Some(year)) => Ok(PuzzleId::new(year, day)),
+            _ => Ok(value.parse()?),
+        }
+    }
+
+    fn free_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        parse_puzzle(&args.free_from_str::<String>()?, year)
+    }
+
+    fn require_year(year: Option<Year>) -> Result<Year, Box<dyn std::error::Error>> {
+        year.ok_or_else(|| {
+            "no year given. Use `--year` or set `AOC_YEAR` in \".cargo/config.toml\"".into()
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         tracing_subscriber::fmt::init();
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: require_year(parse_year(&mut args)?)?,
                 release: args.contains("--release"),
+                store: args.contains("--store"),
+                jobs: parse_jobs(&mut args)?,
+                limits: parse_limits(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = if args.contains("--compare") {
+                    Some(
+                        args.opt_value_from_str("--threshold")?
+                            .unwrap_or(time::DEFAULT_REGRESSION_THRESHOLD),
+                    )
+                } else {
+                    None
+                };
+                let dhat = args.contains("--dhat");
+                let jobs = parse_jobs(&mut args)?;
+                let limits = parse_limits(&mut args)?;
+                let year = parse_year(&mut args)?;
+
+                // NOTE: a puzzle with an explicit year overrides `--year`.
+                let puzzle = match args.opt_free_from_str::<String>()? {
+                    Some(value) => Some(parse_puzzle(&value, year)?),
+                    None => None,
+                };
 
                 AppArguments::Time {
+                    year: match puzzle {
+                        Some(puzzle) => puzzle.year,
+                        None => require_year(year)?,
+                    },
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: puzzle.map(|puzzle| puzzle.day),
                     store,
+                    compare,
+                    jobs,
+                    limits,
+                    dhat,
                 }
             }
+            // NOTE: internal, used by `all` and `time` to run a single puzzle in a child process.
+            Some(CHILD_COMMAND) => AppArguments::RunIsolated {
+                time: args.contains("--time"),
+                memory_limit: args.opt_value_from_str("--memory-limit")?,
+                puzzle: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: free_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                puzzle: free_puzzle(&mut args)?,
             },
+            Some("extract") => {
+                let overwrite = args.contains("--overwrite");
+                AppArguments::Extract {
+                    puzzle: free_puzzle(&mut args)?,
+                    overwrite,
+                }
+            }
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+                AppArguments::Scaffold {
+                    puzzle: free_puzzle(&mut args)?,
+                    download,
+                    overwrite,
+                    template,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let examples = args.contains("--examples");
+                let params = args.values_from_str("--param")?;
+                let visualize = if args.contains("--visualize-terminal") {
+                    Some(Target::Terminal)
+                } else if args.contains("--visualize") {
+                    Some(Target::Files)
+                } else {
+                    None
+                };
+                let watch = args.contains("--watch");
+                if watch && (submit.is_some() || dhat) {
+                    return Err("`--watch` can not be combined with `--submit` or `--dhat`.".into());
+                }
+                if watch && visualize.is_some() {
+                    return Err("`--watch` can not be combined with `--visualize`.".into());
+                }
+                // NOTE: watch mode already runs the example tests before the input.
+                if watch && examples {
+                    return Err("`--watch` can not be combined with `--examples`.".into());
+                }
+                let part = args.opt_value_from_str("--part")?;
+                let input = args.opt_value_from_str("--input")?;
+                let has_example = args.contains("--example");
+                let puzzle = free_puzzle(&mut args)?;
+                // NOTE: the name of `--example [name]` is optional, so it is the free argument after the puzzle.
+                let example = match has_example {
+                    true => Some(
+                        args.opt_free_from_str()?
+                            .unwrap_or_else(|| DEFAULT_EXAMPLE.to_string()),
+                    ),
+                    false => None,
+                };
+                if watch && (part.is_some() || input.is_some() || example.is_some()) {
+                    return Err(
+                        "`--watch` can not be combined with `--part`, `--input` or `--example`."
+                            .into(),
+                    );
+                }
+                AppArguments::Solve {
+                    puzzle,
+                    release,
+                    dhat,
+                    submit,
+                    examples,
+                    params,
+                    watch,
+                    part,
+                    input,
+                    example,
+                    visualize,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -87,7 +273,8 @@ mod args {
             }
         };
 
-        let remaining = args.finish();
+        // NOTE: a bare `--` is allowed, e.g. `cargo solve 18 -- --param dim=6`.
+        let remaining: Vec<_> = args.finish().into_iter().filter(|x| x != "--").collect();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
@@ -103,33 +290,92 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                store,
+                jobs,
+                limits,
+            } => all::handle(year, release, store, jobs, limits, days::DAYS),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                compare,
+                jobs,
+                limits,
+                dhat,
+            } => time::handle(
+                year,
                 day,
+                all,
+                store,
+                compare,
+                jobs,
+                limits,
+                dhat,
+                days::DAYS,
+            ),
+            AppArguments::RunIsolated {
+                puzzle,
+                time,
+                memory_limit,
+            } => isolation::run_child(puzzle, days::DAYS, time, memory_limit),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Extract { puzzle, overwrite } => extract::handle(puzzle, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                let template = template.as_deref().unwrap_or(scaffold::DEFAULT_TEMPLATE);
+                scaffold::handle(puzzle, overwrite, template);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
+                    extract::handle(puzzle, false);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                examples,
+                params,
+                watch,
+                part,
+                input,
+                example,
+                visualize,
+            } => {
+                if watch {
+                    watch::handle(puzzle, release, &params);
+                } else {
+                    solve::handle(
+                        puzzle,
+                        release,
+                        dhat,
+                        submit,
+                        examples,
+                        &params,
+                        part,
+                        input.as_deref(),
+                        example.as_deref(),
+                        visualize,
+                    );
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false, scaffold::DEFAULT_TEMPLATE);
+                        download::handle(puzzle);
+                        extract::handle(puzzle, false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
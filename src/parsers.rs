@@ -0,0 +1,293 @@
+//! [nom](https://docs.rs/nom) combinators for common shapes of puzzle input.
+//!
+//! Combinators are generic over the nom error type. [`parse_all`] runs a parser on the whole input and turns a failure into an
+//! [`InputError`] that points at the offending input, naming what was expected there.
+//!
+//! ```
+//! # use advent_of_code::parsers::{int_pair, lines, parse_all};
+//! let points: Vec<(i32, i32)> = parse_all("1,2\n-3,4\n", lines(int_pair(","))).unwrap();
+//! assert_eq!(points, [(1, 2), (-3, 4)]);
+//!
+//! let err = parse_all("1,2\n3;4\n", lines(int_pair::<i32, _>(","))).unwrap_err();
+//! assert_eq!(err.offset(), 5);
+//! ```
+use std::str::FromStr;
+
+use glam::IVec2;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, digit1, line_ending, multispace0, one_of, space0},
+    combinator::{all_consuming, cut, map, map_opt, opt, recognize},
+    error::{context, ContextError, ErrorKind, ParseError, VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+
+use crate::template::InputError;
+
+/// Run `parser` on the whole input. Trailing whitespace, e.g. the final newline, is ignored.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<O, InputError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|e| to_input_error(input, e))
+}
+
+fn to_input_error(input: &str, e: nom::Err<VerboseError<&str>>) -> InputError {
+    let e = match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        nom::Err::Incomplete(_) => {
+            return InputError::new("incomplete input", (input.len(), 0));
+        }
+    };
+
+    // NOTE: errors are ordered from the innermost parser outwards, the innermost context is the most specific one.
+    let message = e
+        .errors
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(format!("expected {context}")),
+            _ => None,
+        })
+        .or_else(|| {
+            let (at, kind) = e.errors.first()?;
+            Some(match (kind, at.chars().next()) {
+                (VerboseErrorKind::Char(c), _) => format!("expected `{c}`"),
+                (VerboseErrorKind::Nom(ErrorKind::Eof), _) => "unexpected trailing input".into(),
+                (_, Some(found)) => format!("unexpected `{found}`"),
+                (_, None) => "unexpected end of input".into(),
+            })
+        })
+        .unwrap_or_else(|| "unexpected input".into());
+
+    let at = e.errors.first().map_or("", |(at, _)| *at);
+    let len = at.chars().next().map_or(0, char::len_utf8);
+    InputError::at(input, &at[..len], message)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A decimal integer with an optional sign, parsed into any integer type.
+pub fn integer<'a, T, E>(input: &'a str) -> IResult<&'a str, T, E>
+where
+    T: FromStr,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    let digits = recognize(pair(opt(one_of("+-")), digit1));
+    context("an integer", map_opt(digits, |s: &str| s.parse().ok()))(input)
+}
+
+/// Two integers separated by `separator`, e.g. `3,-4` with `","`.
+pub fn int_pair<'a, T, E>(separator: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T), E>
+where
+    T: FromStr,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    separated_pair(integer, tag(separator), integer)
+}
+
+/// Two integers separated by `separator` as a vector, e.g. `p=3,-4` with `preceded(tag("p="), ivec2(","))`.
+pub fn ivec2<'a, E>(separator: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, IVec2, E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    map(int_pair(separator), |(x, y)| IVec2::new(x, y))
+}
+
+/// A name made of letters, digits and underscores.
+pub fn identifier<'a, E>(input: &'a str) -> IResult<&'a str, &'a str, E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    context(
+        "an identifier",
+        recognize(separated_list1(char('_'), alphanumeric1)),
+    )(input)
+}
+
+/// `key` and `value` separated by `separator`, with optional spaces around the separator, e.g. `x00: 1`.
+pub fn key_value<'a, K, V, E>(
+    key: impl Parser<&'a str, K, E>,
+    separator: &'a str,
+    value: impl Parser<&'a str, V, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V), E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_pair(key, tuple((space0, tag(separator), space0)), value)
+}
+
+/// Values separated by `separator` with optional spaces after it, e.g. `1, 2, 3` with `","`.
+pub fn list<'a, O, E>(
+    separator: &'a str,
+    item: impl Parser<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str>,
+{
+    separated_list1(pair(tag(separator), space0), item)
+}
+
+/// Values separated by commas, see [`list`].
+pub fn comma_list<'a, O, E>(
+    item: impl Parser<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str>,
+{
+    list(",", item)
+}
+
+/// One value per line, up to a blank line or the end of the input.
+///
+/// Every line has to match, so an error points into the offending line instead of its start.
+pub fn lines<'a, O, E>(
+    mut line: impl Parser<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str>,
+{
+    move |input| {
+        let (mut input, first) = line.parse(input)?;
+        let mut values = vec![first];
+
+        loop {
+            let Ok((rest, _)) = line_ending::<_, E>(input) else {
+                return Ok((input, values));
+            };
+            if rest.is_empty() || line_ending::<_, E>(rest).is_ok() {
+                return Ok((input, values));
+            }
+
+            let (rest, value) = cut(|i| line.parse(i))(rest)?;
+            values.push(value);
+            input = rest;
+        }
+    }
+}
+
+/// A blank line between two sections of the input.
+pub fn blank_line<'a, E>(input: &'a str) -> IResult<&'a str, (), E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    context("a blank line", map(pair(line_ending, line_ending), |_| ()))(input)
+}
+
+/// Two sections separated by a blank line, e.g. rules followed by updates.
+pub fn sections<'a, A, B, E>(
+    first: impl Parser<&'a str, A, E>,
+    second: impl Parser<&'a str, B, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (A, B), E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    separated_pair(first, blank_line, second)
+}
+
+/// Any number of sections separated by blank lines, e.g. blocks of a schematic.
+pub fn blocks<'a, O, E>(
+    block: impl Parser<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    separated_list1(blank_line, block)
+}
+
+/// `parser` with optional spaces around it.
+pub fn ws<'a, O, E>(
+    parser: impl Parser<&'a str, O, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    E: ParseError<&'a str>,
+{
+    delimited(space0, parser, space0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+    use nom::{bytes::complete::tag, character::complete::u32, sequence::preceded};
+
+    use super::{
+        blocks, comma_list, identifier, int_pair, integer, ivec2, key_value, lines, parse_all,
+        sections, ws,
+    };
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(parse_all("-12", integer::<i64, _>).unwrap(), -12);
+        assert_eq!(parse_all("+7\n", integer::<u8, _>).unwrap(), 7);
+        assert_eq!(
+            parse_all("p=0,4 v=3,-3", |input| {
+                let (input, p) = preceded(tag("p="), ivec2(","))(input)?;
+                let (input, v) = preceded(tag(" v="), ivec2(","))(input)?;
+                Ok((input, (p, v)))
+            })
+            .unwrap(),
+            (IVec2::new(0, 4), IVec2::new(3, -3))
+        );
+
+        let err = parse_all("300", integer::<u8, _>).unwrap_err();
+        assert_eq!(err.to_string(), "expected an integer");
+        assert_eq!(err.offset(), 0);
+    }
+
+    #[test]
+    fn parses_lines_and_lists() {
+        let parsed = parse_all(
+            "190: 10 19\n3267: 81 40 27\n",
+            lines(key_value(
+                u32,
+                ":",
+                nom::multi::separated_list1(tag(" "), u32),
+            )),
+        );
+        assert_eq!(
+            parsed.unwrap(),
+            vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])]
+        );
+
+        let parsed = parse_all("x00: 1\ny_01:0", lines(key_value(identifier, ":", u32)));
+        assert_eq!(parsed.unwrap(), vec![("x00", 1), ("y_01", 0)]);
+
+        assert_eq!(parse_all("1, 2,3", comma_list(u32)).unwrap(), [1, 2, 3]);
+        assert_eq!(parse_all(" 5 ", ws(u32)).unwrap(), 5);
+    }
+
+    #[test]
+    fn parses_sections() {
+        let input = "47|53\n97|13\n\n75,47\n97,61,53\n";
+        let (rules, updates) = parse_all(
+            input,
+            sections(lines(int_pair::<u32, _>("|")), lines(comma_list(u32))),
+        )
+        .unwrap();
+        assert_eq!(rules, [(47, 53), (97, 13)]);
+        assert_eq!(updates, [vec![75, 47], vec![97, 61, 53]]);
+
+        let parsed = parse_all("1\n2\n\n3\n", blocks(lines(u32)));
+        assert_eq!(parsed.unwrap(), vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn locates_errors() {
+        let err = parse_all("1,2\n3;4\n", lines(int_pair::<i32, _>(","))).unwrap_err();
+        assert_eq!(err.offset(), 5);
+        assert_eq!(err.to_string(), "unexpected `;`");
+
+        let err = parse_all("1,2 3,4", int_pair::<i32, _>(",")).unwrap_err();
+        assert_eq!(err.offset(), 4);
+        assert_eq!(err.to_string(), "unexpected trailing input");
+
+        let err = parse_all("1,x", int_pair::<i32, _>(",")).unwrap_err();
+        assert_eq!(err.offset(), 2);
+        assert_eq!(err.to_string(), "expected an integer");
+    }
+}
@@ -0,0 +1,309 @@
+//! Searches on a [`Grid`]: distance maps, flood fills and shortest paths that also depend on the direction.
+//!
+//! Moves go to the orthogonal neighbours of a position. Whether a move is allowed is decided by a `can_move(from, to)` callback,
+//! e.g. `|_, to| grid[to] != '#'` for walls or `|from, to| grid[to] == grid[from] + 1` for climbing.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use glam::IVec2;
+
+use crate::grid::{Grid, NEIGHBOURS_4};
+
+/// Distance in steps from the nearest start, `None` if the position can not be reached.
+pub fn bfs<T>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = IVec2>,
+    mut can_move: impl FnMut(IVec2, IVec2) -> bool,
+) -> Grid<Option<u32>> {
+    let mut distances = grid.map(|_| None);
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if distances.get(start) == Some(&None) {
+            distances[start] = Some(0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let distance = distances[pos].unwrap() + 1;
+        for next in grid.neighbours4(pos) {
+            if distances[next].is_none() && can_move(pos, next) {
+                distances[next] = Some(distance);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// All positions that can be reached from `start`, in the order of their distance.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: IVec2,
+    can_move: impl FnMut(IVec2, IVec2) -> bool,
+) -> Vec<IVec2> {
+    let distances = bfs(grid, [start], can_move);
+    let mut reached: Vec<_> = distances
+        .iter()
+        .filter_map(|(pos, distance)| Some((distance.as_ref()?, pos)))
+        .collect();
+    reached.sort_by_key(|&(distance, _)| *distance);
+    reached.into_iter().map(|(_, pos)| pos).collect()
+}
+
+/// Label the connected regions of the grid, neighbours are in the same region if `connected` holds for their cells.
+///
+/// Returns the region of every position and the number of regions. Regions are numbered in row-major order of their first cell.
+pub fn label_regions<T>(
+    grid: &Grid<T>,
+    mut connected: impl FnMut(&T, &T) -> bool,
+) -> (Grid<usize>, usize) {
+    let mut labels: Grid<Option<usize>> = grid.map(|_| None);
+    let mut count = 0;
+    let mut queue = Vec::new();
+
+    for start in grid.positions() {
+        if labels[start].is_some() {
+            continue;
+        }
+
+        labels[start] = Some(count);
+        queue.push(start);
+        while let Some(pos) = queue.pop() {
+            for next in grid.neighbours4(pos) {
+                if labels[next].is_none() && connected(&grid[pos], &grid[next]) {
+                    labels[next] = Some(count);
+                    queue.push(next);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    (labels.map(|label| label.unwrap()), count)
+}
+
+/// The positions of every region of equal cells, see [`label_regions`].
+pub fn regions<T: PartialEq>(grid: &Grid<T>) -> Vec<Vec<IVec2>> {
+    let (labels, count) = label_regions(grid, T::eq);
+    let mut regions = vec![Vec::new(); count];
+    for (pos, &label) in labels.iter() {
+        regions[label].push(pos);
+    }
+    regions
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Shortest distances from a start and to an end, e.g. to find shortcuts along the way.
+pub struct DistanceMaps {
+    pub from_start: Grid<Option<u32>>,
+    pub to_end: Grid<Option<u32>>,
+}
+
+impl DistanceMaps {
+    pub fn new<T>(
+        grid: &Grid<T>,
+        start: IVec2,
+        end: IVec2,
+        mut can_move: impl FnMut(IVec2, IVec2) -> bool,
+    ) -> Self {
+        Self {
+            from_start: bfs(grid, [start], &mut can_move),
+            // NOTE: searching backwards from the end, so moves are reversed.
+            to_end: bfs(grid, [end], |from, to| can_move(to, from)),
+        }
+    }
+
+    /// Length of the shortest path from start to end.
+    pub fn shortest(&self) -> Option<u32> {
+        let end = self.to_end.find(|&distance| distance == Some(0))?;
+        self.from_start[end]
+    }
+
+    /// Length of the shortest path from start to end that passes `pos`.
+    pub fn through(&self, pos: IVec2) -> Option<u32> {
+        Some((*self.from_start.get(pos)?)? + (*self.to_end.get(pos)?)?)
+    }
+
+    /// All positions that are part of any shortest path.
+    pub fn on_shortest_paths(&self) -> impl Iterator<Item = IVec2> + '_ {
+        let shortest = self.shortest();
+        self.from_start
+            .positions()
+            .filter(move |&pos| shortest.is_some() && self.through(pos) == shortest)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Costs of moving forward and of turning by 90 degrees on the spot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TurnCosts {
+    pub step: u32,
+    pub turn: u32,
+}
+
+/// Lowest costs of every position and direction, see [`dijkstra_turns`].
+pub struct DirectedCosts {
+    /// One grid per direction of [`NEIGHBOURS_4`].
+    costs: [Grid<Option<u32>>; 4],
+}
+
+impl DirectedCosts {
+    /// Lowest cost to stand at `pos`, facing `dir`.
+    pub fn get(&self, pos: IVec2, dir: IVec2) -> Option<u32> {
+        *self.costs[direction_index(dir)].get(pos)?
+    }
+
+    /// Lowest cost to stand at `pos`, facing any direction.
+    pub fn min_at(&self, pos: IVec2) -> Option<u32> {
+        self.costs.iter().filter_map(|costs| *costs.get(pos)?).min()
+    }
+}
+
+/// # Panics
+/// Panics if `dir` is not one of [`NEIGHBOURS_4`].
+fn direction_index(dir: IVec2) -> usize {
+    NEIGHBOURS_4
+        .iter()
+        .position(|&d| d == dir)
+        .unwrap_or_else(|| panic!("{dir} is not an orthogonal direction"))
+}
+
+/// Dijkstra over positions and the direction they are entered in. Every state can move forward or turn left or right.
+///
+/// `starts` are positions with the direction they face.
+pub fn dijkstra_turns<T>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = (IVec2, IVec2)>,
+    costs: TurnCosts,
+    mut can_move: impl FnMut(IVec2, IVec2) -> bool,
+) -> DirectedCosts {
+    let unvisited = grid.map(|_| None);
+    let mut result = DirectedCosts {
+        costs: [0; 4].map(|_| unvisited.clone()),
+    };
+    let mut queue = BinaryHeap::new();
+
+    for (pos, dir) in starts {
+        queue.push(Reverse((0, pos.x, pos.y, direction_index(dir))));
+    }
+
+    while let Some(Reverse((cost, x, y, dir))) = queue.pop() {
+        let pos = IVec2::new(x, y);
+        match result.costs[dir].get_mut(pos) {
+            Some(Some(_)) | None => continue,
+            Some(visited) => *visited = Some(cost),
+        }
+
+        let next = pos + NEIGHBOURS_4[dir];
+        if grid.contains(next) && can_move(pos, next) {
+            queue.push(Reverse((cost + costs.step, next.x, next.y, dir)));
+        }
+        for turned in [(dir + 1) % 4, (dir + 3) % 4] {
+            queue.push(Reverse((cost + costs.turn, x, y, turned)));
+        }
+    }
+
+    result
+}
+
+/// Cost of the cheapest paths from `start` facing `dir` to `end`, and all positions on any of these paths.
+pub fn shortest_path_tiles<T>(
+    grid: &Grid<T>,
+    (start, dir): (IVec2, IVec2),
+    end: IVec2,
+    costs: TurnCosts,
+    mut can_move: impl FnMut(IVec2, IVec2) -> bool,
+) -> Option<(u32, Vec<IVec2>)> {
+    let forward = dijkstra_turns(grid, [(start, dir)], costs, &mut can_move);
+    let best = forward.min_at(end)?;
+
+    // NOTE: walk back from every direction the end is reached in at the best cost, facing the other way.
+    let ends = NEIGHBOURS_4
+        .into_iter()
+        .filter(|&dir| forward.get(end, dir) == Some(best))
+        .map(|dir| (end, -dir));
+    let backward = dijkstra_turns(grid, ends, costs, |from, to| can_move(to, from));
+
+    let tiles = grid
+        .positions()
+        .filter(|&pos| {
+            NEIGHBOURS_4.into_iter().any(|dir| {
+                let there = forward.get(pos, dir);
+                let back = backward.get(pos, -dir);
+                there.zip(back).is_some_and(|(a, b)| a + b == best)
+            })
+        })
+        .collect();
+
+    Some((best, tiles))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use glam::IVec2;
+
+    use super::{
+        bfs, flood_fill, label_regions, regions, shortest_path_tiles, DistanceMaps, TurnCosts,
+    };
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<char> {
+        Grid::parse("#####\n#S..#\n#.#.#\n#..E#\n#####", Some).unwrap()
+    }
+
+    #[test]
+    fn maps_distances() {
+        let grid = maze();
+        let open = |_, to: IVec2| grid[to] != '#';
+
+        let distances = bfs(&grid, [IVec2::ONE], open);
+        assert_eq!(distances[IVec2::new(3, 3)], Some(4));
+        assert_eq!(distances[IVec2::ZERO], None);
+        assert_eq!(flood_fill(&grid, IVec2::ONE, open).len(), 8);
+
+        let maps = DistanceMaps::new(&grid, IVec2::ONE, IVec2::new(3, 3), open);
+        assert_eq!(maps.shortest(), Some(4));
+        assert_eq!(maps.through(IVec2::new(3, 1)), Some(4));
+        assert_eq!(maps.on_shortest_paths().count(), 8);
+    }
+
+    #[test]
+    fn labels_regions() {
+        let grid = Grid::parse("AAB\nABB\nCCA", Some).unwrap();
+
+        let (labels, count) = label_regions(&grid, |a, b| a == b);
+        assert_eq!(count, 4);
+        assert_eq!(labels.to_string(), "001\n011\n223");
+
+        let sizes: Vec<_> = regions(&grid).iter().map(Vec::len).collect();
+        assert_eq!(sizes, [3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn finds_paths_with_turns() {
+        let grid = maze();
+        let costs = TurnCosts {
+            step: 1,
+            turn: 1000,
+        };
+
+        let (cost, tiles) = shortest_path_tiles(
+            &grid,
+            (IVec2::ONE, IVec2::X),
+            IVec2::new(3, 3),
+            costs,
+            |_, to| grid[to] != '#',
+        )
+        .unwrap();
+
+        assert_eq!(cost, 1004);
+        assert_eq!(tiles.len(), 5);
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// Answers are stored per year, e.g. `./data/2024/answers.json`.
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/answers.json")
+}
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Result of comparing an answer with the accepted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerStatus {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl AnswerStatus {
+    /// Compare an answer with the expected answer, if one is known.
+    pub fn of(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match expected {
+            None => AnswerStatus::Unknown,
+            Some(expected) if Some(expected) == actual => AnswerStatus::Pass,
+            Some(expected) => AnswerStatus::Fail {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+impl Display for AnswerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerStatus::Pass => write!(f, "pass"),
+            AnswerStatus::Fail { expected } => write!(f, "FAIL (expected `{expected}`)"),
+            AnswerStatus::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate the answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of a year from a JSON file. If not present, returns empty answers.
+    /// A file that can not be read or parsed is an error, so that it is not overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => Answers::try_from(json).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day)
+            .and_then(|a| a.part(part))
+    }
+
+    /// Set the accepted answer for one part of a day.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(answer.into()),
+            2 => self.data[index].part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Fill in parts that are missing in `self` from `other`. Existing answers are never overwritten.
+    pub fn merge_missing(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+
+        for answer in &other.data {
+            for part in [1, 2] {
+                if let (None, Some(value)) = (merged.get(answer.day, part), answer.part(part)) {
+                    merged.set(answer.day, part, value);
+                }
+            }
+        }
+
+        merged
+    }
+
+    /// Compare the answer a solution produced with the accepted answer.
+    pub fn check(&self, day: Day, part: u8, actual: Option<&str>) -> AnswerStatus {
+        AnswerStatus::of(self.get(day, part), actual)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, AnswerStatus, Answers};
+    use crate::day;
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("42".into()),
+                part_2: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.check(day!(1), 1, Some("42")), AnswerStatus::Pass);
+        assert_eq!(
+            answers.check(day!(1), 1, Some("43")),
+            AnswerStatus::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, None),
+            AnswerStatus::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(day!(1), 2, Some("1")), AnswerStatus::Unknown);
+        assert_eq!(answers.check(day!(2), 1, Some("1")), AnswerStatus::Unknown);
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(Answers::try_from("{".to_string()).is_err());
+    }
+
+    #[test]
+    fn merges_missing_answers_only() {
+        let answers = get_mock_answers();
+        let mut other = Answers::default();
+        other.set(day!(1), 1, "0");
+        other.set(day!(1), 2, "7");
+        other.set(day!(3), 1, "8");
+
+        let merged = answers.merge_missing(&other);
+        assert_eq!(merged.get(day!(1), 1), Some("42"));
+        assert_eq!(merged.get(day!(1), 2), Some("7"));
+        assert_eq!(merged.get(day!(3), 1), Some("8"));
+        assert_eq!(merged.data.len(), 2);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap().data.len(), 1);
+    }
+}
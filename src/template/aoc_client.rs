@@ -0,0 +1,469 @@
+/// Native client for the Advent of Code website.
+///
+/// Talks to the website over HTTP using the session cookie of a logged-in user.
+/// The base URL can be overridden with the `AOC_BASE_URL` environment variable, e.g. to run against a mock server.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/slowteetoe/aoc2024 by steven.lotito@gmail.com";
+
+/// Hint returned by the website alongside a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie in `AOC_SESSION` or `~/.adventofcode.session`.
+    MissingSession,
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+    /// The server responded with a non-success status code.
+    HttpStatus(u16),
+    /// The server rejected a submission because one was made too recently.
+    RateLimited(Option<Duration>),
+    /// The submitted answer is not correct.
+    WrongAnswer(AnswerHint),
+    /// The submitted part is either already solved or not unlocked yet.
+    WrongLevel,
+    /// The response did not contain what we expected.
+    UnexpectedResponse,
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::HttpStatus(400 | 401) => write!(
+                f,
+                "server responded with status 400, the session cookie is probably invalid or expired."
+            ),
+            AocClientError::HttpStatus(404) => write!(
+                f,
+                "server responded with status 404, the puzzle is probably not unlocked yet."
+            ),
+            AocClientError::HttpStatus(code) => write!(f, "server responded with status {code}."),
+            AocClientError::RateLimited(Some(wait)) => write!(
+                f,
+                "you gave an answer too recently, wait {}s before submitting again.",
+                wait.as_secs()
+            ),
+            AocClientError::RateLimited(None) => {
+                write!(f, "you gave an answer too recently, wait before submitting again.")
+            }
+            AocClientError::WrongAnswer(AnswerHint::TooHigh) => {
+                write!(f, "that's not the right answer, your answer is too high.")
+            }
+            AocClientError::WrongAnswer(AnswerHint::TooLow) => {
+                write!(f, "that's not the right answer, your answer is too low.")
+            }
+            AocClientError::WrongAnswer(AnswerHint::None) => {
+                write!(f, "that's not the right answer.")
+            }
+            AocClientError::WrongLevel => write!(
+                f,
+                "this part is either already solved or not unlocked yet."
+            ),
+            AocClientError::UnexpectedResponse => {
+                write!(f, "could not make sense of the server response.")
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, _) => AocClientError::HttpStatus(code),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Create a client configured by `AOC_BASE_URL` and `AOC_SESSION` / `~/.adventofcode.session`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the input of a puzzle.
+    pub fn fetch_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle));
+        self.get(&url)
+    }
+
+    /// Fetch the description of a puzzle. Returns the HTML of all unlocked parts.
+    pub fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        let articles = html::elements(&html, "article");
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+
+        Ok(articles.join("\n\n"))
+    }
+
+    /// Submit an answer for one part of a puzzle. Returns `Ok` if the answer was accepted.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        let article = html::elements(&html, "article")
+            .into_iter()
+            .next()
+            .ok_or(AocClientError::UnexpectedResponse)?;
+
+        parse_submission(&html::to_text(&article))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+}
+
+/// Map the text of a submission response to a result.
+fn parse_submission(text: &str) -> Result<(), AocClientError> {
+    if text.contains("That's the right answer") {
+        Ok(())
+    } else if text.contains("You gave an answer too recently") {
+        Err(AocClientError::RateLimited(parse_wait_time(text)))
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("your answer is too high") {
+            AnswerHint::TooHigh
+        } else if text.contains("your answer is too low") {
+            AnswerHint::TooLow
+        } else {
+            AnswerHint::None
+        };
+        Err(AocClientError::WrongAnswer(hint))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Err(AocClientError::WrongLevel)
+    } else {
+        Err(AocClientError::UnexpectedResponse)
+    }
+}
+
+/// Parse the remaining wait time of a rate limit response, e.g. "You have 1m 5s left to wait."
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let wait_str = text
+        .split("You have ")
+        .nth(1)?
+        .split(" left to wait")
+        .next()?;
+
+    wait_str
+        .split_whitespace()
+        .map(|s| {
+            if let Some(minutes) = s.strip_suffix('m') {
+                Some(minutes.parse::<u64>().ok()? * 60)
+            } else {
+                s.strip_suffix('s')?.parse().ok()
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+pub fn read(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle_path = puzzle.data_path("puzzles", "html");
+
+    let description = client.fetch_puzzle(puzzle)?;
+    write_file(&puzzle_path, &description)?;
+
+    Ok(html::to_text(&description))
+}
+
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "html");
+
+    let input = client.fetch_input(puzzle)?;
+    let description = client.fetch_puzzle(puzzle)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &description)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<(), AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+/// Write a data file, creating the directory of its year if needed.
+fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Just enough HTML handling to deal with the markup of the puzzle pages.
+pub(crate) mod html {
+    /// Returns the outer HTML of all elements with the given tag name. Does not handle nested elements of the same tag.
+    pub fn elements(html: &str, tag: &str) -> Vec<String> {
+        let open = format!("<{tag}");
+        let close = format!("</{tag}>");
+        let mut result = vec![];
+        let mut rest = html;
+
+        while let Some(start) = rest.find(&open) {
+            let Some(len) = rest[start..].find(&close) else {
+                break;
+            };
+            let end = start + len + close.len();
+            result.push(rest[start..end].to_string());
+            rest = &rest[end..];
+        }
+
+        result
+    }
+
+    /// Strips all tags and decodes common entities.
+    pub fn to_text(html: &str) -> String {
+        let mut text = String::with_capacity(html.len());
+        let mut in_tag = false;
+
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+
+        decode_entities(&text)
+    }
+
+    pub fn decode_entities(s: &str) -> String {
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait_time, AnswerHint, AocClient, AocClientError};
+    use crate::puzzle;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    /// Serves a single request with the given status and body, returning the raw request through the channel.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            tx.send(request).unwrap();
+        });
+
+        (format!("http://{addr}"), rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session_cookie() {
+        let (url, rx) = mock_server(200, "1 2\n3 4\n");
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(client.fetch_input(puzzle!(2024, 1)).unwrap(), "1 2\n3 4\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn fetches_puzzle_articles() {
+        let (url, _) = mock_server(
+            200,
+            "<main><article class=\"day-desc\"><h2>Part 1</h2></article><p>x</p><article class=\"day-desc\"><h2>Part 2</h2></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(
+            client.fetch_puzzle(puzzle!(2024, 2)).unwrap(),
+            "<article class=\"day-desc\"><h2>Part 1</h2></article>\n\n<article class=\"day-desc\"><h2>Part 2</h2></article>"
+        );
+    }
+
+    #[test]
+    fn maps_status_codes() {
+        let (url, _) = mock_server(404, "Not found");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.fetch_input(puzzle!(2024, 1)),
+            Err(AocClientError::HttpStatus(404))
+        ));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, rx) = mock_server(
+            200,
+            "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+        );
+        let client = AocClient::new(&url, "abc");
+        assert!(client.submit(puzzle!(2024, 3), 2, "42").is_ok());
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn handles_wrong_answers() {
+        let (url, _) = mock_server(200, "<article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article>");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.submit(puzzle!(2024, 3), 1, "42"),
+            Err(AocClientError::WrongAnswer(AnswerHint::TooHigh))
+        ));
+    }
+
+    #[test]
+    fn handles_rate_limits() {
+        let (url, _) = mock_server(200, "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.submit(puzzle!(2024, 3), 1, "42"),
+            Err(AocClientError::RateLimited(Some(d))) if d == Duration::from_secs(65)
+        ));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait_time("You have 30s left to wait."),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_wait_time("You have 5ö left to wait."), None);
+        assert_eq!(parse_wait_time("Please wait."), None);
+    }
+
+    #[test]
+    fn handles_solved_levels() {
+        let (url, _) = mock_server(200, "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.submit(puzzle!(2024, 3), 1, "42"),
+            Err(AocClientError::WrongLevel)
+        ));
+    }
+}
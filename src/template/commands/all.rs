@@ -1,5 +1,83 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::answers::Answers;
+use crate::template::isolation::Limits;
+use crate::template::ledger::Ledger;
+use crate::template::registry::DaySolution;
+use crate::template::run_multi::{run_multi, Profile};
+use crate::template::{all_days, PuzzleId, Year};
+
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    store: bool,
+    jobs: Option<usize>,
+    limits: Limits,
+    solutions: &[DaySolution],
+) {
+    let summary = run_multi(
+        year,
+        &all_days().collect(),
+        solutions,
+        if is_release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        },
+        false,
+        jobs,
+        limits,
+    );
+
+    let stored = !store || store_answers(year, &summary.answers);
+
+    if !stored || !summary.regressions.is_empty() || !summary.exceeded_limits.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Record the answers that the submission ledger confirms as correct, for parts that do not have an accepted answer yet.
+/// Answers of this run that were never submitted successfully are unverified and only listed.
+fn store_answers(year: Year, answers: &Answers) -> bool {
+    let accepted = match Answers::read_from_file(year) {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            eprintln!("Not storing answers: invalid answers file {e}");
+            return false;
+        }
+    };
+
+    let mut verified = Answers::default();
+    let mut unverified = vec![];
+
+    for answer in &answers.data {
+        let ledger = Ledger::read_from_file(PuzzleId::new(year, answer.day)).unwrap_or_else(|e| {
+            eprintln!("Invalid submission ledger {e}");
+            Ledger::new(PuzzleId::new(year, answer.day))
+        });
+
+        for part in [1, 2] {
+            if accepted.get(answer.day, part).is_some() || answer.part(part).is_none() {
+                continue;
+            }
+            match ledger.solution(part) {
+                Some(solution) => verified.set(answer.day, part, solution),
+                None => unverified.push(format!("day {} part {part}", answer.day)),
+            }
+        }
+    }
+
+    if let Err(e) = accepted.merge_missing(&verified).store_file(year) {
+        eprintln!("Failed to store answers: {e}");
+        return false;
+    }
+
+    println!("Stored answers.");
+    if !unverified.is_empty() {
+        println!(
+            "Not stored, unverified without a correct submission: {}.",
+            unverified.join(", ")
+        );
+    }
+    true
 }
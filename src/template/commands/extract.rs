@@ -0,0 +1,62 @@
+use std::{fs, process};
+
+use crate::template::examples::{self, Examples, ExtractedExample};
+use crate::template::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let puzzle_path = puzzle.data_path("puzzles", "html");
+    let description = match fs::read_to_string(&puzzle_path) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle description \"{puzzle_path}\": {e}. Run `cargo download {puzzle}` first."
+            );
+            process::exit(1);
+        }
+    };
+
+    let extracted = examples::extract(puzzle, &description);
+    if extracted.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    }
+
+    let manifest_path = puzzle.data_path("examples", "json");
+    let mut manifest = match fs::read_to_string(&manifest_path) {
+        Ok(json) => Examples::try_from(json).unwrap_or_else(|e| {
+            eprintln!("Failed to read examples manifest \"{manifest_path}\": {e}");
+            process::exit(1);
+        }),
+        Err(_) => Examples { data: vec![] },
+    };
+
+    for ExtractedExample { example, input } in extracted {
+        let path = example.path(puzzle);
+
+        // NOTE: `scaffold` creates an empty example file, which is fine to replace.
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !existing.trim().is_empty() && existing != input && !overwrite {
+            println!("Skipped \"{path}\", it already exists. Use `--overwrite` to replace it.");
+            continue;
+        }
+
+        if let Err(e) = fs::write(&path, &input) {
+            eprintln!("Failed to write example file \"{path}\": {e}");
+            process::exit(1);
+        }
+
+        println!(
+            "🎄 Successfully wrote example `{}` to \"{path}\".",
+            example.name
+        );
+        manifest.merge(example, overwrite);
+    }
+
+    match manifest.store_file(puzzle) {
+        Ok(()) => println!("🎄 Successfully wrote expected answers to \"{manifest_path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write examples manifest \"{manifest_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,15 +1,13 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+pub fn handle(puzzle: PuzzleId) {
+    match aoc_client::read(puzzle) {
+        Ok(puzzle) => println!("{puzzle}"),
+        Err(e) => {
+            eprintln!("failed to read puzzle {puzzle}: {e}");
+            process::exit(1);
+        }
     };
 }
@@ -1,13 +1,115 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::html;
+use crate::template::PuzzleId;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Name of the template that is used without `--template`.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// User-defined templates, e.g. `templates/grid.txt`. They take precedence over built-in templates of the same name.
+const USER_TEMPLATES_DIR: &str = "templates";
+
+/// Built-in templates for common puzzle shapes, selectable with `--template <name>`.
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        DEFAULT_TEMPLATE,
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "numbers",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/numbers.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+    (
+        "sections",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/sections.txt"
+        )),
+    ),
+    (
+        "nom",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/nom.txt"
+        )),
+    ),
+];
+
+/// Look up a template by name, preferring a user-defined template.
+fn load_template(name: &str) -> Result<String, String> {
+    let user_path = format!("{USER_TEMPLATES_DIR}/{name}.txt");
+    if let Ok(template) = fs::read_to_string(&user_path) {
+        return Ok(template);
+    }
+
+    TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| {
+            let names: Vec<_> = TEMPLATES.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown template `{name}`, expected one of: {} or a file in \"{USER_TEMPLATES_DIR}/\".",
+                names.join(", ")
+            )
+        })
+}
+
+/// Title of a puzzle from its downloaded description, e.g. "Historian Hysteria".
+fn puzzle_title(puzzle: PuzzleId) -> Option<String> {
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "html")).ok()?;
+    let heading = html::to_text(html::elements(&description, "h2").first()?);
+    let (_, title) = heading
+        .trim_matches(|c| c == '-' || c == ' ')
+        .split_once(": ")?;
+    Some(title.to_string())
+}
+
+/// Replace the placeholders of a template:
+///
+/// - `%YEAR%`: e.g. `2024`
+/// - `%DAY%`: padded day, e.g. `01`
+/// - `%DAY_NUMBER%`: e.g. `1`
+/// - `%PUZZLE%`: e.g. `2024-01`
+/// - `%TITLE%`: title of the puzzle if its description was downloaded, e.g. `Historian Hysteria`, otherwise `Day 1`
+/// - `%URL%`: e.g. `https://adventofcode.com/2024/day/1`
+fn render(template: &str, puzzle: PuzzleId, title: Option<&str>) -> String {
+    let day_number = puzzle.day.into_inner().to_string();
+    let title = title.map_or_else(|| format!("Day {day_number}"), str::to_string);
+
+    template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%PUZZLE%", &puzzle.to_string())
+        .replace("%TITLE%", &title)
+        .replace(
+            "%URL%",
+            &format!("https://adventofcode.com/{}/day/{day_number}", puzzle.year),
+        )
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -20,6 +122,9 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +132,18 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: &str) {
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +153,8 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let title = puzzle_title(puzzle);
+    match file.write_all(render(&template, puzzle, title.as_deref()).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -75,5 +185,63 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_template, render, TEMPLATES};
+    use crate::puzzle;
+    use crate::template::runner::InputSource;
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "%PUZZLE%: solution!(%YEAR%, %DAY_NUMBER%, Day%DAY%) %TITLE% %URL%";
+
+        assert_eq!(
+            render(template, puzzle!(2024, 1), Some("Historian Hysteria")),
+            "2024-01: solution!(2024, 1, Day01) Historian Hysteria https://adventofcode.com/2024/day/1"
+        );
+        assert!(render(template, puzzle!(2024, 12), None)
+            .ends_with("Day 12 https://adventofcode.com/2024/day/12"));
+    }
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, template) in TEMPLATES {
+            assert!(load_template(name).is_ok());
+            assert!(template.contains("Day%DAY%"), "template `{name}`");
+        }
+
+        assert!(load_template("hexagons")
+            .unwrap_err()
+            .contains("expected one of: default, grid"));
+    }
+
+    /// The built-in templates, rendered by `build.rs`.
+    mod rendered {
+        include!(concat!(env!("OUT_DIR"), "/templates.rs"));
+    }
+
+    #[test]
+    fn builtin_templates_run_on_empty_examples() {
+        let mut names: Vec<_> = TEMPLATES.iter().map(|(name, _)| *name).collect();
+        let mut rendered: Vec<_> = rendered::TEMPLATES.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        rendered.sort_unstable();
+        assert_eq!(rendered, names);
+
+        // NOTE: `scaffold` creates an empty example, which the `example_tests!` of a new day runs on.
+        for (name, run) in rendered::TEMPLATES {
+            let mut errors = vec![];
+            let source = InputSource {
+                path: "01.txt",
+                text: "",
+            };
+            run(source, false, &mut |record| errors.extend(record.error));
+            assert_eq!(errors, Vec::<String>::new(), "template `{name}`");
+        }
+    }
 }
@@ -1,9 +1,23 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::counting_alloc;
+use crate::template::PuzzleId;
+use crate::visualize::Target;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    examples: bool,
+    params: &[String],
+    part: Option<u8>,
+    input: Option<&str>,
+    example: Option<&str>,
+    visualize: Option<Target>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -12,12 +26,48 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+        if counting_alloc::is_enabled() {
+            cmd_args.extend([
+                "--features".to_string(),
+                counting_alloc::FEATURE.to_string(),
+            ]);
+        }
     }
 
     cmd_args.push("--".to_string());
 
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if let Some(example) = example {
+        cmd_args.push("--example".to_string());
+        cmd_args.push(example.to_string());
+    }
+
+    if let Some(target) = visualize {
+        cmd_args.push(target.flag().to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -1,16 +1,37 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::isolation::Limits;
+use crate::template::registry::DaySolution;
+use crate::template::run_multi::{run_multi, Profile};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings_comparison::{compare, print_table};
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Parts that got slower than this (in percent) are reported as regressions by `--compare`.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    jobs: Option<usize>,
+    limits: Limits,
+    dhat: bool,
+    solutions: &[DaySolution],
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run every day that has a baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +42,27 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: with `--dhat`, every day runs as a child process built with `dhat-heap`, which records its heap usage.
+    let (solutions, profile) = match dhat {
+        true => (&[][..], Profile::Dhat),
+        false => (solutions, Profile::Release),
+    };
+
+    let summary = run_multi(year, &days_to_run, solutions, profile, true, jobs, limits);
+    let timings = summary.timings.unwrap();
+
+    let has_regressions = compare_threshold.is_some_and(|threshold| {
+        let deltas = compare(&stored_timings, &timings);
+        print_table(&deltas, threshold);
+        deltas.iter().any(|delta| delta.is_regression(threshold))
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +71,8 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions || !summary.exceeded_limits.is_empty() {
+        process::exit(1);
+    }
 }
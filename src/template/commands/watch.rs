@@ -0,0 +1,204 @@
+//! Watch mode of `cargo solve <day> --watch`.
+//!
+//! Re-runs the example tests and then the real input whenever the source, the examples or the input of the day change.
+//! Files are polled, so that no platform-specific file watching is needed.
+use std::{
+    collections::BTreeMap,
+    fs,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::examples::Examples;
+use crate::template::records::{PartRecord, PARSE_STEP};
+use crate::template::run_multi::child_commands::format_record;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Files have to be unchanged for this long before a run starts, so that rapid saves trigger a single run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Answers of a run by part.
+type RunAnswers = BTreeMap<u8, Option<String>>;
+
+/// Modification times of the watched files.
+type Snapshot = Vec<(String, Option<SystemTime>)>;
+
+pub fn handle(puzzle: PuzzleId, release: bool, params: &[String]) {
+    let mut previous: Option<RunAnswers> = None;
+
+    loop {
+        let snapshot = snapshot(puzzle);
+
+        println!("{ANSI_BOLD}{puzzle}{ANSI_RESET}");
+        println!("------");
+
+        // NOTE: keep the answers of the last successful build to compare against after a compile error.
+        if let Some(answers) = run_once(puzzle, release, params, previous.as_ref()) {
+            previous = Some(answers);
+        }
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+        wait_for_change(puzzle, snapshot);
+        println!();
+    }
+}
+
+/// The source of the day, its input, its examples manifest and all example files.
+fn watched_files(puzzle: PuzzleId) -> Vec<String> {
+    let mut files = vec![
+        puzzle.bin_path(),
+        puzzle.data_path("inputs", "txt"),
+        puzzle.data_path("examples", "json"),
+    ];
+
+    match Examples::read_from_file(puzzle) {
+        Ok(examples) => files.extend(examples.data.iter().map(|e| e.path(puzzle))),
+        Err(_) => files.push(puzzle.data_path("examples", "txt")),
+    }
+
+    files
+}
+
+fn snapshot(puzzle: PuzzleId) -> Snapshot {
+    watched_files(puzzle)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Block until a watched file changed and all files have settled for [`DEBOUNCE`].
+fn wait_for_change(puzzle: PuzzleId, mut last: Snapshot) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(puzzle);
+        if current != last {
+            last = current;
+            break;
+        }
+    }
+
+    loop {
+        thread::sleep(DEBOUNCE);
+        let current = snapshot(puzzle);
+        if current == last {
+            return;
+        }
+        last = current;
+    }
+}
+
+fn cargo(args: &[&str], release: bool) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.args(args);
+    if release {
+        cmd.arg("--release");
+    }
+
+    cmd.status().is_ok_and(|status| status.success())
+}
+
+/// Build the day, run its tests and then the real input. Returns the answers, or `None` if the build failed.
+fn run_once(
+    puzzle: PuzzleId,
+    release: bool,
+    params: &[String],
+    previous: Option<&RunAnswers>,
+) -> Option<RunAnswers> {
+    let bin = puzzle.to_string();
+
+    if !cargo(&["build", "--quiet", "--bin", &bin], release) {
+        println!("✖ Build failed.");
+        return None;
+    }
+
+    println!("{ANSI_ITALIC}Example tests{ANSI_RESET}");
+    if !cargo(&["test", "--quiet", "--bin", &bin], release) {
+        println!("✖ Example tests failed.");
+    }
+
+    println!("{ANSI_ITALIC}Input{ANSI_RESET}");
+    let mut args = vec!["run", "--quiet", "--bin", &bin];
+    if release {
+        args.push("--release");
+    }
+    args.extend(["--", "--format", "json"]);
+    for param in params {
+        args.extend(["--param", param]);
+    }
+
+    let output = match Command::new("cargo")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run {puzzle}: {e}");
+            return None;
+        }
+    };
+
+    let mut answers = RunAnswers::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some(record) = PartRecord::from_json_line(line) else {
+            println!("{line}");
+            continue;
+        };
+
+        if record.part == PARSE_STEP {
+            println!("{}", format_record(&record));
+            continue;
+        }
+
+        let change = previous.and_then(|previous| {
+            let before = previous.get(&record.part)?;
+            Some(answer_change(before.as_deref(), record.answer.as_deref()))
+        });
+
+        match change {
+            Some(change) => println!(
+                "{} {ANSI_ITALIC}{change}{ANSI_RESET}",
+                format_record(&record)
+            ),
+            None => println!("{}", format_record(&record)),
+        }
+
+        answers.insert(record.part, record.answer);
+    }
+
+    Some(answers)
+}
+
+/// Describe how an answer changed since the previous run.
+fn answer_change(before: Option<&str>, after: Option<&str>) -> String {
+    match (before, after) {
+        (before, after) if before == after => "(unchanged)".into(),
+        (Some(before), _) => format!("(was {before})"),
+        (None, _) => "(was no answer)".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::answer_change;
+
+    #[test]
+    fn describes_answer_changes() {
+        assert_eq!(answer_change(Some("41"), Some("41")), "(unchanged)");
+        assert_eq!(answer_change(None, None), "(unchanged)");
+        assert_eq!(answer_change(Some("41"), Some("42")), "(was 41)");
+        assert_eq!(answer_change(Some("41"), None), "(was 41)");
+        assert_eq!(answer_change(None, Some("42")), "(was no answer)");
+    }
+}
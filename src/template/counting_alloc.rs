@@ -0,0 +1,174 @@
+//! A lightweight global allocator that counts allocations, enabled with the `count-allocations` feature.
+//!
+//! Unlike dhat it adds only a few atomic operations to every allocation, so it can stay enabled while benchmarking.
+//! The runner only measures the first, cold execution of every part, benchmark samples are not counted, see [`crate::template::runner`].
+//! The counters are process-wide, so in-process days are run one at a time while counting, see [`crate::template::run_multi`].
+//!
+//! NOTE: the allocator is installed by the library instead of `solution!`, because the main binary links every solution as a module and a binary can only have one global allocator.
+//! It is left out with `dhat-heap`, which installs its own allocator.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::template::stats::MemoryStats;
+
+/// Name of the cargo feature, forwarded to child `cargo` invocations.
+pub const FEATURE: &str = "count-allocations";
+
+/// Whether allocations are counted in this build.
+pub const fn is_enabled() -> bool {
+    cfg!(all(
+        feature = "count-allocations",
+        not(feature = "dhat-heap")
+    ))
+}
+
+#[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc::new();
+
+/// Forwards to the system allocator and keeps process-wide counters.
+pub struct CountingAlloc {
+    allocations: AtomicU64,
+    total_bytes: AtomicU64,
+    current_bytes: AtomicU64,
+    peak_bytes: AtomicU64,
+}
+
+impl CountingAlloc {
+    pub const fn new() -> Self {
+        Self {
+            allocations: AtomicU64::new(0),
+            total_bytes: AtomicU64::new(0),
+            current_bytes: AtomicU64::new(0),
+            peak_bytes: AtomicU64::new(0),
+        }
+    }
+
+    fn record_alloc(&self, size: u64) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.total_bytes.fetch_add(size, Ordering::Relaxed);
+        let current = self.current_bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(&self, size: u64) {
+        self.current_bytes.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    /// Start a new measurement: reset the counters and the peak to the bytes that are currently allocated.
+    pub fn start(&self) -> Measurement<'_> {
+        let baseline = self.current_bytes.load(Ordering::Relaxed);
+        self.allocations.store(0, Ordering::Relaxed);
+        self.total_bytes.store(0, Ordering::Relaxed);
+        self.peak_bytes.store(baseline, Ordering::Relaxed);
+        Measurement {
+            alloc: self,
+            baseline,
+        }
+    }
+}
+
+impl Default for CountingAlloc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.record_dealloc(layout.size() as u64);
+    }
+
+    // NOTE: a reallocation counts as a new allocation of the new size, like in dhat.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record_dealloc(layout.size() as u64);
+            self.record_alloc(new_size as u64);
+        }
+        new_ptr
+    }
+}
+
+/// A running measurement, see [`CountingAlloc::start`].
+pub struct Measurement<'a> {
+    alloc: &'a CountingAlloc,
+    baseline: u64,
+}
+
+impl Measurement<'_> {
+    /// Heap usage since the measurement started. The peak does not include memory that was allocated before.
+    pub fn stats(&self) -> MemoryStats {
+        MemoryStats {
+            total_bytes: self.alloc.total_bytes.load(Ordering::Relaxed),
+            peak_bytes: self
+                .alloc
+                .peak_bytes
+                .load(Ordering::Relaxed)
+                .saturating_sub(self.baseline),
+            allocations: self.alloc.allocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Start a measurement on the global allocator, `None` if allocations are not counted in this build.
+pub fn start() -> Option<Measurement<'static>> {
+    #[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+    return Some(ALLOC.start());
+
+    #[cfg(not(all(feature = "count-allocations", not(feature = "dhat-heap"))))]
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use super::CountingAlloc;
+
+    #[test]
+    fn counts_allocations() {
+        let alloc = CountingAlloc::new();
+        let small = Layout::from_size_align(16, 8).unwrap();
+        let large = Layout::from_size_align(64, 8).unwrap();
+
+        unsafe {
+            let kept = alloc.alloc(small);
+            let measurement = alloc.start();
+
+            let a = alloc.alloc(large);
+            let b = alloc.alloc_zeroed(small);
+            alloc.dealloc(a, large);
+            let b = alloc.realloc(b, small, 32);
+
+            let stats = measurement.stats();
+            assert_eq!(stats.allocations, 3);
+            assert_eq!(stats.total_bytes, 64 + 16 + 32);
+            assert_eq!(stats.peak_bytes, 64 + 16);
+
+            alloc.dealloc(b, Layout::from_size_align(32, 8).unwrap());
+            alloc.dealloc(kept, small);
+        }
+    }
+}
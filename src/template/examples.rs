@@ -0,0 +1,538 @@
+/// Example inputs of a puzzle with their expected answers.
+///
+/// Examples are listed in a manifest next to the example files, e.g. `data/2024/examples/16.json`:
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "name": "default", "file": "16.txt", "part_1": "11048", "part_2": "64" },
+///     { "name": "small", "file": "16-small.txt", "part_1": "7036" }
+///   ]
+/// }
+/// ```
+///
+/// Examples can also override the [parameters](crate::template::params) of a puzzle, e.g. `"params": { "dim": "6" }`.
+/// Expected answers are optional. Without a manifest, a puzzle has a single example `default` that reads `<day>.txt`.
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::AnswerStatus;
+use crate::template::aoc_client::html;
+use crate::template::params;
+use crate::template::records::PartRecord;
+use crate::template::registry::RunSolution;
+use crate::template::runner::InputSource;
+use crate::template::PuzzleId;
+
+/// Name of the example that is used when a puzzle has no manifest.
+pub const DEFAULT_EXAMPLE: &str = "default";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    /// File name relative to the examples folder of the year.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameter overrides for this example.
+    pub params: HashMap<String, String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Path of the example file, e.g. `data/2024/examples/16-small.txt`.
+    pub fn path(&self, puzzle: PuzzleId) -> String {
+        format!("data/{}/examples/{}", puzzle.year, self.file)
+    }
+
+    /// Run a solution on this example without benching and return its records.
+    /// `overrides` take precedence over the parameters of the example.
+    pub fn run(
+        &self,
+        puzzle: PuzzleId,
+        run: RunSolution,
+        overrides: &HashMap<String, String>,
+    ) -> Result<Vec<PartRecord>, String> {
+        let path = self.path(puzzle);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("could not open example file \"{path}\": {e}"))?;
+
+        let mut params = self.params.clone();
+        params.extend(overrides.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let mut records = vec![];
+        params::with_overrides(&params, || {
+            run(
+                InputSource {
+                    path: &path,
+                    text: &text,
+                },
+                false,
+                &mut |record| records.push(record),
+            );
+        });
+        Ok(records)
+    }
+
+    /// Compare the answers of a run with the expected answers, for each part that has a record.
+    pub fn check(&self, records: &[PartRecord]) -> Vec<(u8, AnswerStatus)> {
+        records
+            .iter()
+            .filter(|record| matches!(record.part, 1 | 2))
+            .map(|record| {
+                let status = AnswerStatus::of(self.expected(record.part), record.answer.as_deref());
+                (record.part, status)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    /// Read the examples manifest of a puzzle. If not present, returns the default example.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(puzzle.data_path("examples", "json")) {
+            Ok(json) => Examples::try_from(json),
+            Err(_) => Ok(Examples {
+                data: vec![Example {
+                    name: DEFAULT_EXAMPLE.into(),
+                    file: format!("{}.txt", puzzle.day),
+                    part_1: None,
+                    part_2: None,
+                    params: HashMap::new(),
+                }],
+            }),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Example> {
+        self.data.iter().find(|e| e.name == name)
+    }
+
+    /// Write the examples manifest of a puzzle.
+    pub fn store_file(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let path = puzzle.data_path("examples", "json");
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    /// Add an example, or fill in the expected answers of the example with the same name.
+    /// Known answers are only replaced if `overwrite` is set.
+    pub fn merge(&mut self, example: Example, overwrite: bool) {
+        let Some(existing) = self.data.iter_mut().find(|e| e.name == example.name) else {
+            self.data.push(example);
+            return;
+        };
+
+        for (current, new) in [
+            (&mut existing.part_1, example.part_1),
+            (&mut existing.part_2, example.part_2),
+        ] {
+            if new.is_some() && (current.is_none() || overwrite) {
+                *current = new;
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An example input found in a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedExample {
+    pub example: Example,
+    pub input: String,
+}
+
+/// Extract the examples of a puzzle from its description, as saved by `cargo download`.
+///
+/// The first `<pre><code>` block of a part is its example input, the last highlighted `<code><em>` value of the part its expected answer.
+/// A part without a block of its own uses the example of part one. The first example is `default` (`<day>.txt`),
+/// a different example of part two is `second` (`<day>-2.txt`).
+pub fn extract(puzzle: PuzzleId, description: &str) -> Vec<ExtractedExample> {
+    let mut extracted: Vec<ExtractedExample> = vec![];
+
+    for (part, article) in (1..=2).zip(html::elements(description, "article")) {
+        let index = match first_block(&article) {
+            Some(input) => match extracted.iter().position(|e| e.input == input) {
+                Some(index) => index,
+                None => {
+                    let (name, file) = match extracted.len() {
+                        0 => (DEFAULT_EXAMPLE.into(), format!("{}.txt", puzzle.day)),
+                        n => ("second".into(), format!("{}-{}.txt", puzzle.day, n + 1)),
+                    };
+                    extracted.push(ExtractedExample {
+                        example: Example {
+                            name,
+                            file,
+                            part_1: None,
+                            part_2: None,
+                            params: HashMap::new(),
+                        },
+                        input,
+                    });
+                    extracted.len() - 1
+                }
+            },
+            None if extracted.is_empty() => continue,
+            None => 0,
+        };
+
+        let example = &mut extracted[index].example;
+        match part {
+            1 => example.part_1 = last_answer(&article),
+            _ => example.part_2 = last_answer(&article),
+        }
+    }
+
+    extracted
+}
+
+/// Text of the first `<pre>` block of an article.
+fn first_block(article: &str) -> Option<String> {
+    html::elements(article, "pre")
+        .first()
+        .map(|block| html::to_text(block))
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// The last highlighted value of an article, e.g. `<code><em>11</em></code>`.
+fn last_answer(article: &str) -> Option<String> {
+    ["<code><em>", "<em><code>"]
+        .iter()
+        .filter_map(|open| {
+            let start = article.rfind(open)? + open.len();
+            let len = article[start..].find("</")?;
+            Some((start, &article[start..start + len]))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| html::decode_entities(answer.trim()))
+        .filter(|answer| !answer.is_empty())
+}
+
+/// Run a single example and panic if an answer does not match the expected answer. Used by [`crate::example_tests`].
+pub fn assert_example(puzzle: PuzzleId, name: &str, run: RunSolution) {
+    let examples = Examples::read_from_file(puzzle)
+        .unwrap_or_else(|e| panic!("invalid examples manifest for {puzzle}: {e}"));
+
+    let example = examples
+        .get(name)
+        .unwrap_or_else(|| panic!("example `{name}` is not listed for {puzzle}"));
+
+    let records = example
+        .run(puzzle, run, &HashMap::new())
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    for record in &records {
+        if let Some(error) = &record.error {
+            panic!("example `{name}` part {}: {error}", record.part);
+        }
+    }
+
+    for (part, status) in example.check(&records) {
+        assert!(
+            !matches!(status, AnswerStatus::Fail { .. }),
+            "example `{name}` part {part}: got `{}`, {status}",
+            records
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.answer.as_deref())
+                .unwrap_or("no answer"),
+        );
+    }
+}
+
+/// Generates one test per named example of the manifest. Use it inside the `tests` module of a solution:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     advent_of_code::example_tests!(default, small);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($( $name:ident ),* $(,)?) => {
+        mod examples {
+            $(
+                #[test]
+                fn $name() {
+                    $crate::template::examples::assert_example(
+                        super::super::PUZZLE,
+                        stringify!($name),
+                        super::super::__run_in_process,
+                    );
+                }
+            )*
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Quote a string as JSON.
+fn json_string(value: &str) -> String {
+    JsonValue::String(value.into()).stringify().unwrap()
+}
+
+impl Examples {
+    /// The manifest as JSON, one example per line. Keys are written in a fixed order, so rewrites do not churn diffs.
+    pub fn to_json(&self) -> String {
+        let examples: Vec<String> = self
+            .data
+            .iter()
+            .map(|example| format!("    {}", example.to_json()))
+            .collect();
+        format!("{{\n  \"examples\": [\n{}\n  ]\n}}\n", examples.join(",\n"))
+    }
+}
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(Examples {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Example {
+    /// The example as a JSON object with the keys `name`, `file`, `part_1`, `part_2` and `params`, see [`Examples::to_json`].
+    fn to_json(&self) -> String {
+        let mut fields = vec![
+            format!("\"name\": {}", json_string(&self.name)),
+            format!("\"file\": {}", json_string(&self.file)),
+        ];
+
+        for (key, part) in [("part_1", &self.part_1), ("part_2", &self.part_2)] {
+            if let Some(answer) = part {
+                fields.push(format!("\"{key}\": {}", json_string(answer)));
+            }
+        }
+
+        if !self.params.is_empty() {
+            let mut params: Vec<_> = self.params.iter().collect();
+            params.sort();
+            let params: Vec<String> = params
+                .into_iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_string(value)))
+                .collect();
+            fields.push(format!("\"params\": {{ {} }}", params.join(", ")));
+        }
+
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let params = match json.get("params") {
+            Some(params) => params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected example.params to be an object.")?
+                .iter()
+                .map(|(name, value)| match value.get::<String>() {
+                    Some(value) => Ok((name.clone(), value.clone())),
+                    None => Err(format!("Expected example.params.{name} to be a string.")),
+                })
+                .collect::<Result<_, _>>()?,
+            None => HashMap::new(),
+        };
+
+        Ok(Example {
+            name: string("name").ok_or("Expected example.name to be a string.")?,
+            file: string("file").ok_or("Expected example.file to be a string.")?,
+            part_1: string("part_1"),
+            part_2: string("part_2"),
+            params,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, Example, Examples};
+    use crate::template::{answers::AnswerStatus, records::PartRecord};
+    use crate::{day, puzzle};
+    use std::collections::HashMap;
+
+    fn example(name: &str, part_1: Option<&str>, part_2: Option<&str>) -> Example {
+        Example {
+            name: name.into(),
+            file: format!("{name}.txt"),
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            params: HashMap::new(),
+        }
+    }
+
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            day: day!(16),
+            part,
+            answer: answer.map(Into::into),
+            stats: None,
+            error: None,
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let json = r#"{
+            "examples": [
+                { "name": "default", "file": "16.txt", "part_1": "11048", "part_2": "64" },
+                { "name": "small", "file": "16-small.txt", "part_1": "7036", "part_2": null, "params": { "dim": "6" } }
+            ]
+        }"#;
+
+        let examples = Examples::try_from(json.to_string()).unwrap();
+        assert_eq!(examples.data.len(), 2);
+
+        let small = examples.get("small").unwrap();
+        assert_eq!(small.file, "16-small.txt");
+        assert_eq!(small.expected(1), Some("7036"));
+        assert_eq!(small.expected(2), None);
+        assert_eq!(small.params["dim"], "6");
+        assert!(examples.get("default").unwrap().params.is_empty());
+        assert!(examples.get("large").is_none());
+
+        assert!(Examples::try_from(r#"{ "examples": [{ "name": "x" }] }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn extracts_examples() {
+        let description = "<article class=\"day-desc\"><h2>--- Day 3 ---</h2>\
+            <p>For example:</p><pre><code>xmul(2,4)&amp;mul[3,7]\n</code></pre>\
+            <p>Adding up the results produces <code><em>161</em></code>.</p></article>\n\n\
+            <article class=\"day-desc\"><h2>--- Part Two ---</h2>\
+            <pre><code>xmul(2,4)&amp;don't()<em>mul(8,5)</em>\n</code></pre>\
+            <p>This time, the sum is <em><code>48</code></em>.</p></article>";
+
+        let extracted = extract(puzzle!(2024, 3), description);
+        assert_eq!(extracted.len(), 2);
+
+        assert_eq!(extracted[0].input, "xmul(2,4)&mul[3,7]\n");
+        assert_eq!(extracted[0].example.name, "default");
+        assert_eq!(extracted[0].example.file, "03.txt");
+        assert_eq!(extracted[0].example.expected(1), Some("161"));
+        assert_eq!(extracted[0].example.expected(2), None);
+
+        assert_eq!(extracted[1].input, "xmul(2,4)&don't()mul(8,5)\n");
+        assert_eq!(extracted[1].example.name, "second");
+        assert_eq!(extracted[1].example.file, "03-2.txt");
+        assert_eq!(extracted[1].example.expected(2), Some("48"));
+
+        // NOTE: part two without a block of its own uses the default example.
+        let description =
+            "<article><pre><code>1\n2\n</code></pre><p><code><em>3</em></code></p></article>\
+            <article><p>Now it is <code><em>2</em></code>.</p></article>";
+        let extracted = extract(puzzle!(2024, 1), description);
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(extracted[0].example.expected(1), Some("3"));
+        assert_eq!(extracted[0].example.expected(2), Some("2"));
+    }
+
+    #[test]
+    fn merges_manifests() {
+        let mut examples = Examples {
+            data: vec![example("default", Some("161"), None)],
+        };
+
+        examples.merge(example("default", Some("160"), Some("48")), false);
+        examples.merge(example("second", None, Some("48")), false);
+        assert_eq!(
+            examples.data[0],
+            example("default", Some("161"), Some("48"))
+        );
+        assert_eq!(examples.data[1], example("second", None, Some("48")));
+
+        examples.merge(example("default", Some("160"), None), true);
+        assert_eq!(
+            examples.data[0],
+            example("default", Some("160"), Some("48"))
+        );
+
+        let json = examples.to_json();
+        assert_eq!(Examples::try_from(json.clone()).unwrap(), examples);
+        examples.data[1].params =
+            HashMap::from([("dim".into(), "6".into()), ("bytes".into(), "12".into())]);
+        assert!(examples
+            .to_json()
+            .contains(r#""params": { "bytes": "12", "dim": "6" }"#));
+        examples.data[1].params.clear();
+        assert_eq!(
+            json,
+            "{\n  \"examples\": [\n    { \"name\": \"default\", \"file\": \"default.txt\", \"part_1\": \"160\", \"part_2\": \"48\" },\n    { \"name\": \"second\", \"file\": \"second.txt\", \"part_2\": \"48\" }\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn checks_records() {
+        let example = Example {
+            name: "small".into(),
+            file: "16-small.txt".into(),
+            part_1: Some("7036".into()),
+            part_2: None,
+            params: HashMap::new(),
+        };
+
+        let checks = example.check(&[
+            record(0, None),
+            record(1, Some("7036")),
+            record(2, Some("45")),
+        ]);
+        assert_eq!(
+            checks,
+            vec![(1, AnswerStatus::Pass), (2, AnswerStatus::Unknown)]
+        );
+
+        let checks = example.check(&[record(1, None)]);
+        assert_eq!(
+            checks,
+            vec![(
+                1,
+                AnswerStatus::Fail {
+                    expected: "7036".into()
+                }
+            )]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use miette::{Diagnostic, SourceSpan};
+
+/// Malformed puzzle input.
+///
+/// When a part returns this error, the runner renders a report that points at the offending line and column of the input file.
+///
+/// ```
+/// # use advent_of_code::template::InputError;
+/// let input = "#.S\n#X.";
+/// let line = input.lines().nth(1).unwrap();
+/// let err = InputError::at(input, &line[1..2], "unexpected symbol `X`");
+/// assert_eq!(err.offset(), 5);
+/// ```
+#[derive(Debug, Diagnostic)]
+pub struct InputError {
+    message: String,
+    #[label("here")]
+    span: SourceSpan,
+}
+
+impl InputError {
+    /// An error at a byte offset and length in the input.
+    pub fn new(message: impl Into<String>, span: impl Into<SourceSpan>) -> Self {
+        Self {
+            message: message.into(),
+            span: span.into(),
+        }
+    }
+
+    /// An error pointing at `fragment`, which must be a slice of `input`.
+    ///
+    /// # Panics
+    /// Panics if `fragment` is not a slice of `input`.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + fragment.len() <= input.len())
+            .expect("fragment is a slice of the input");
+
+        Self::new(message, (offset, fragment.len()))
+    }
+
+    /// Byte offset of the error in the input.
+    pub fn offset(&self) -> usize {
+        self.span.offset()
+    }
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputError;
+
+    #[test]
+    fn locates_fragments() {
+        let input = "#.S\n#X.\n";
+        let line = input.lines().nth(1).unwrap();
+
+        let err = InputError::at(input, &line[1..2], "unexpected symbol `X`");
+        assert_eq!(err.offset(), 5);
+        assert_eq!(err.to_string(), "unexpected symbol `X`");
+    }
+
+    #[test]
+    #[should_panic(expected = "fragment is a slice of the input")]
+    fn rejects_foreign_fragments() {
+        let other = String::from("X");
+        let _ = InputError::at("#.S", &other, "unexpected symbol");
+    }
+}
@@ -0,0 +1,236 @@
+/// Runs solutions in a child process of the main binary, so that parts can be stopped when they exceed a limit.
+/// Used by `cargo all` and `cargo time` when `--timeout` or `--memory-limit` is set.
+use std::{
+    env, fs,
+    io::{self, BufRead, BufReader, Read},
+    process::{self, Command, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::records::{Limit, PartRecord};
+use crate::template::registry::{self, DaySolution};
+use crate::template::run_multi::{child_commands::format_record, DayOutput};
+use crate::template::runner::InputSource;
+use crate::template::PuzzleId;
+
+/// Name of the hidden subcommand that runs a single puzzle inside the child process.
+pub const CHILD_COMMAND: &str = "__run-isolated";
+
+/// Rust prints this message before aborting when an allocation fails.
+const ALLOCATION_FAILURE: &str = "memory allocation of";
+
+/// Limits enforced on every part of a solution.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    /// Maximum time a single part may take, including benchmarking.
+    pub timeout: Option<Duration>,
+    /// Maximum address space of the child process in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+}
+
+/// Run a solution in a child process and write its records to `output`.
+/// Parts that exceed a limit are reported with a [`Limit`], parts after them are reported as not run.
+pub fn run_isolated(
+    solution: &DaySolution,
+    is_timed: bool,
+    limits: Limits,
+    output: &mut DayOutput,
+) -> Vec<PartRecord> {
+    let mut cmd = match env::current_exe() {
+        Ok(exe) => Command::new(exe),
+        Err(e) => {
+            output.err(format!("Could not locate the current executable: {e}"));
+            return vec![];
+        }
+    };
+
+    cmd.args([CHILD_COMMAND, &solution.puzzle.to_string()]);
+
+    if is_timed {
+        cmd.arg("--time");
+    }
+
+    if let Some(memory) = limits.memory {
+        cmd.args(["--memory-limit", &memory.to_string()]);
+    }
+
+    let mut child = match cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            output.err(format!("Could not start child process: {e}"));
+            return vec![];
+        }
+    };
+
+    // NOTE: stdout is read on a separate thread, so that waiting for the next record can time out.
+    let (tx, rx) = mpsc::channel();
+    let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+    thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_thread = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let mut records: Vec<PartRecord> = vec![];
+    let mut timed_out = false;
+    let mut deadline = limits.timeout.map(|t| Instant::now() + t);
+
+    loop {
+        let line = match deadline {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Timeout) => {
+                        timed_out = true;
+                        break;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            None => match rx.recv() {
+                Ok(line) => line,
+                Err(_) => break,
+            },
+        };
+
+        match PartRecord::from_json_line(&line) {
+            Some(record) => {
+                output.out(format_record(&record));
+                records.push(record);
+                deadline = limits.timeout.map(|t| Instant::now() + t);
+            }
+            None => output.out(line),
+        }
+    }
+
+    if timed_out {
+        let _ = child.kill();
+    }
+
+    let status = child.wait();
+    let stderr = stderr_thread.join().unwrap_or_default();
+    stderr.lines().for_each(|line| output.err(line));
+
+    let limit = if timed_out {
+        Some(Limit::Timeout)
+    } else if stderr.contains(ALLOCATION_FAILURE) {
+        Some(Limit::OutOfMemory)
+    } else {
+        None
+    };
+
+    let mut culprit = true;
+
+    for &part in solution.parts {
+        if records.iter().any(|r| r.part == part) {
+            continue;
+        }
+
+        // NOTE: parts run in order, so only the first missing part hit the limit.
+        let error = match (culprit, limit, &status) {
+            (true, Some(Limit::Timeout), _) => format!(
+                "exceeded timeout of {:.1?}",
+                limits.timeout.unwrap_or_default()
+            ),
+            (true, Some(Limit::OutOfMemory), _) => format!(
+                "exceeded memory limit of {} MiB",
+                limits.memory.unwrap_or_default() / 1024 / 1024
+            ),
+            (true, None, Ok(status)) => format!("child process exited with {status}"),
+            (true, None, Err(e)) => format!("child process failed: {e}"),
+            (false, _, _) => "not run".into(),
+        };
+
+        let record = PartRecord {
+            day: solution.puzzle.day,
+            part,
+            answer: None,
+            stats: None,
+            error: Some(error),
+            limit: limit.filter(|_| culprit),
+        };
+
+        output.out(format_record(&record));
+        records.push(record);
+        culprit = false;
+    }
+
+    records
+}
+
+/// Entry point of the child process: run a puzzle and print one JSON record per part as soon as the part finishes.
+pub fn run_child(
+    puzzle: PuzzleId,
+    solutions: &[DaySolution],
+    is_timed: bool,
+    memory_limit: Option<u64>,
+) {
+    if let Some(bytes) = memory_limit {
+        if let Err(e) = apply_memory_limit(bytes) {
+            eprintln!("Could not apply memory limit: {e}");
+            process::exit(1);
+        }
+    }
+
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        eprintln!("Puzzle {puzzle} is not linked into this binary.");
+        process::exit(1);
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let Ok(input) = fs::read_to_string(&input_path) else {
+        eprintln!("Could not open input file \"{input_path}\".");
+        process::exit(1);
+    };
+
+    let source = InputSource {
+        path: &input_path,
+        text: &input,
+    };
+
+    (solution.run)(source, is_timed, &mut |record| {
+        println!("{}", record.to_json_line());
+    });
+}
+
+/// Limit the address space of the current process.
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)]
+fn apply_memory_limit(bytes: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes.into(),
+        rlim_max: bytes.into(),
+    };
+
+    // SAFETY: `setrlimit` only reads the passed struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_memory_limit(_bytes: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory limits are only supported on unix.",
+    ))
+}
@@ -0,0 +1,401 @@
+/// Persistent record of submitted answers, one file per puzzle.
+/// Used to avoid submitting answers that are known to be wrong, or parts that are already solved.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AnswerHint, AocClientError};
+use crate::template::PuzzleId;
+
+/// The verdict of the website for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+}
+
+impl Verdict {
+    /// Maps the result of a submission to a verdict. Returns `None` for results that do not judge the answer.
+    pub fn from_submission(submission: &Result<(), AocClientError>) -> Option<Self> {
+        match submission {
+            Ok(()) => Some(Verdict::Correct),
+            Err(AocClientError::WrongAnswer(AnswerHint::TooHigh)) => Some(Verdict::TooHigh),
+            Err(AocClientError::WrongAnswer(AnswerHint::TooLow)) => Some(Verdict::TooLow),
+            Err(AocClientError::WrongAnswer(AnswerHint::None)) => Some(Verdict::Wrong),
+            Err(AocClientError::RateLimited(_)) => Some(Verdict::RateLimited),
+            Err(_) => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    Duplicate(Verdict),
+    AboveKnownBound(String),
+    BelowKnownBound(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer `{answer}`.")
+            }
+            Refusal::Duplicate(verdict) => {
+                write!(f, "this answer was submitted before and was `{verdict}`.")
+            }
+            Refusal::AboveKnownBound(bound) => {
+                write!(
+                    f,
+                    "this answer is not below `{bound}`, which is known to be too high."
+                )
+            }
+            Refusal::BelowKnownBound(bound) => {
+                write!(
+                    f,
+                    "this answer is not above `{bound}`, which is known to be too low."
+                )
+            }
+        }
+    }
+}
+
+/// All submissions for a single puzzle.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug)]
+pub struct Ledger {
+    pub puzzle: PuzzleId,
+    pub submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    pub fn new(puzzle: PuzzleId) -> Self {
+        Self {
+            puzzle,
+            submissions: vec![],
+        }
+    }
+
+    /// Dehydrate the ledger to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = self.puzzle.data_path("submissions", "json");
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger from a JSON file. If not present, returns an empty ledger.
+    /// A file that can not be read or parsed is an error, so that its history is not overwritten.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        let path = puzzle.data_path("submissions", "json");
+        match fs::read_to_string(&path) {
+            Ok(json) => Ledger::try_from((puzzle, json)).map_err(|e| format!("\"{path}\": {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Ledger::new(puzzle)),
+            Err(e) => Err(format!("\"{path}\": {e}")),
+        }
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, verdict: Verdict) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.submissions.push(Submission {
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        });
+    }
+
+    /// Returns the accepted answer of a part, if any.
+    pub fn solution(&self, part: u8) -> Option<&str> {
+        self.submissions_for(part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Check whether an answer should be submitted, based on the verdicts of earlier submissions.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(solution) = self.solution(part) {
+            return Err(Refusal::AlreadySolved(solution.to_string()));
+        }
+
+        // NOTE: rate-limited submissions were never judged, so they may be repeated.
+        if let Some(previous) = self
+            .submissions_for(part)
+            .find(|s| s.answer == answer && s.verdict != Verdict::RateLimited)
+        {
+            return Err(Refusal::Duplicate(previous.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let known_bound = |verdict: Verdict| {
+            self.submissions_for(part)
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, bound)) = known_bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min_by_key(|(high, _)| *high)
+        {
+            return Err(Refusal::AboveKnownBound(bound.clone()));
+        }
+
+        if let Some((_, bound)) = known_bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max_by_key(|(low, _)| *low)
+        {
+            return Err(Refusal::BelowKnownBound(bound.clone()));
+        }
+
+        Ok(())
+    }
+
+    fn submissions_for(&self, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions.iter().filter(move |s| s.part == part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Ledger> for JsonValue {
+    fn from(value: Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<(PuzzleId, String)> for Ledger {
+    type Error = String;
+
+    fn try_from((puzzle, value): (PuzzleId, String)) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("submissions")
+            .ok_or("expected JSON document to have key `submissions`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.submissions` to be an array.")?;
+
+        Ok(Ledger {
+            puzzle,
+            submissions: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|v| v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|v| v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Refusal, Verdict};
+    use crate::puzzle;
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::new(puzzle!(2024, 1));
+        ledger.record(1, "100", Verdict::TooHigh);
+        ledger.record(1, "80", Verdict::TooHigh);
+        ledger.record(1, "10", Verdict::TooLow);
+        ledger.record(1, "50", Verdict::Wrong);
+        ledger.record(1, "40", Verdict::RateLimited);
+        ledger
+    }
+
+    #[test]
+    fn refuses_duplicates() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(1, "50"),
+            Err(Refusal::Duplicate(Verdict::Wrong))
+        );
+    }
+
+    #[test]
+    fn allows_rate_limited_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(1, "40"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(1, "90"),
+            Err(Refusal::AboveKnownBound("80".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "5"),
+            Err(Refusal::BelowKnownBound("10".into()))
+        );
+        assert_eq!(ledger.check(1, "79"), Ok(()));
+    }
+
+    #[test]
+    fn ignores_bounds_of_other_parts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(2, "90"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut ledger = get_mock_ledger();
+        ledger.record(1, "42", Verdict::Correct);
+        assert_eq!(
+            ledger.check(1, "43"),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let json = tinyjson::JsonValue::from(ledger).stringify().unwrap();
+        let parsed = Ledger::try_from((puzzle!(2024, 1), json)).unwrap();
+        assert_eq!(parsed.submissions.len(), 5);
+        assert_eq!(parsed.submissions[2].answer, "10");
+        assert_eq!(parsed.submissions[2].verdict, Verdict::TooLow);
+    }
+
+    #[test]
+    fn reads_missing_ledger_as_empty() {
+        let ledger = Ledger::read_from_file(puzzle!(2015, 25)).unwrap();
+        assert!(ledger.submissions.is_empty());
+        assert!(Ledger::try_from((puzzle!(2015, 25), "{".to_string())).is_err());
+    }
+}
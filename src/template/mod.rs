@@ -1,68 +1,164 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod counting_alloc;
+pub mod examples;
+pub mod isolation;
+pub mod params;
+pub mod records;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use input_error::*;
+pub use puzzle::*;
+pub use solution::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod input_error;
+mod ledger;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod solution;
+mod stats;
 mod timings;
+mod timings_comparison;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2024/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(puzzle.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `PUZZLE` and `DAY` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+/// Passing a type implementing [`Solution`] instead runs its parse step once and both parts on the parsed value.
+///
+/// Parts return either an `Option` or a `Result`, see [`IntoAnswer`]. Errors are printed as a report that points into the input file.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1] [part_two, 2]);
     };
+    ($year:expr, $day:expr, $solution:ty) => {
+        $crate::solution!(@common $year, $day);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        fn main() {
+            use $crate::template::runner::*;
+            if is_examples_run() {
+                run_examples(PUZZLE, __PARAMS, __run_in_process);
+                return;
+            }
+            let part = part_arg(__PARTS);
+            let input = input_args(PUZZLE, __PARAMS);
+            $crate::template::params::with_overrides(&input.params, || {
+                run_solution::<$solution>(input.source(), PUZZLE, part);
+            });
+        }
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// Parts of this solution, see [`$crate::template::registry`].
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub const __PARTS: &[u8] = &[$crate::template::records::PARSE_STEP, 1, 2];
+
+        /// Entry point for the in-process runner, see [`$crate::template::registry`].
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __run_in_process(
+            source: $crate::template::runner::InputSource,
+            is_timed: bool,
+            on_record: &mut dyn FnMut($crate::template::records::PartRecord),
+        ) {
+            use $crate::template::runner::*;
+            run_solution_records::<$solution>(source, PUZZLE, None, is_timed, on_record);
+        }
+    };
+
+    (@impl $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $year, $day);
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if is_examples_run() {
+                run_examples(PUZZLE, __PARAMS, __run_in_process);
+                return;
+            }
+            let part = part_arg(__PARTS);
+            let input = input_args(PUZZLE, __PARAMS);
+            let source = input.source();
+            $crate::template::params::with_overrides(&input.params, || {
+                $(
+                    if part.is_none_or(|part| part == $part) {
+                        run_part($func, source.text, source, PUZZLE, $part);
+                    }
+                )*
+            });
         }
+
+        /// Parts of this solution, see [`$crate::template::registry`].
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub const __PARTS: &[u8] = &[$( $part ),*];
+
+        /// Entry point for the in-process runner, see [`$crate::template::registry`].
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __run_in_process(
+            source: $crate::template::runner::InputSource,
+            is_timed: bool,
+            on_record: &mut dyn FnMut($crate::template::records::PartRecord),
+        ) {
+            use $crate::template::runner::*;
+            $( on_record(run_part_record($func, source.text, source, PUZZLE, $part, is_timed)); )*
+        }
+    };
+
+    (@common $year:expr, $day:expr) => {
+        // NOTE: provides an empty `__PARAMS`, shadowed by the one of `params!`.
+        #[allow(unused_imports)]
+        use $crate::template::params::defaults::*;
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::puzzle!($year, $day);
+
+        /// The current day.
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = PUZZLE.day;
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
     };
 }
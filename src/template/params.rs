@@ -0,0 +1,189 @@
+/// Per-puzzle parameters, e.g. the grid size of a puzzle whose examples use a smaller grid than the real input.
+///
+/// Parameters are declared with [`crate::params`] and have a default value.
+/// Examples override them with `params` in the examples manifest, `cargo solve` with `--param <name>=<value>`.
+/// Overrides are stored per thread, so that tests and parallel runs do not affect each other.
+use std::{cell::RefCell, collections::HashMap, fmt::Display, str::FromStr};
+
+thread_local! {
+    static OVERRIDES: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Run `f` with the given parameter overrides. Overrides of an enclosing call are kept unless overridden again.
+pub fn with_overrides<T>(overrides: &HashMap<String, String>, f: impl FnOnce() -> T) -> T {
+    let previous = OVERRIDES.with(|current| {
+        let mut current = current.borrow_mut();
+        let previous = current.clone();
+        current.extend(overrides.iter().map(|(k, v)| (k.clone(), v.clone())));
+        previous
+    });
+
+    // NOTE: restore the previous overrides even if `f` panics, e.g. in a test.
+    struct Restore(Option<HashMap<String, String>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                OVERRIDES.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+/// Parse `<name>=<value>` pairs, e.g. the values of `--param`.
+pub fn parse_overrides(pairs: &[String]) -> Result<HashMap<String, String>, String> {
+    pairs
+        .iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) if !name.is_empty() => Ok((name.into(), value.into())),
+            _ => Err(format!("expected `<name>=<value>`, got `{pair}`.")),
+        })
+        .collect()
+}
+
+/// The value of a parameter: the override if there is one, `default` otherwise.
+///
+/// # Panics
+/// Panics if the override is not a valid value of `T`.
+pub fn get<T: FromStr>(name: &str, default: T) -> T
+where
+    T::Err: Display,
+{
+    OVERRIDES.with(|overrides| match overrides.borrow().get(name) {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value `{value}` for parameter `{name}`: {e}")),
+        None => default,
+    })
+}
+
+/// Check that every override names a declared parameter.
+pub fn check_overrides(
+    overrides: &HashMap<String, String>,
+    declared: &[&str],
+) -> Result<(), String> {
+    let mut unknown: Vec<_> = overrides
+        .keys()
+        .filter(|name| !declared.contains(&name.as_str()))
+        .collect();
+    unknown.sort();
+
+    match (unknown.first(), declared.is_empty()) {
+        (None, _) => Ok(()),
+        (Some(name), true) => Err(format!(
+            "unknown parameter `{name}`, this puzzle has no parameters."
+        )),
+        (Some(name), false) => Err(format!(
+            "unknown parameter `{name}`, expected one of: {}.",
+            declared.join(", ")
+        )),
+    }
+}
+
+/// Check that every current override names a declared parameter.
+///
+/// # Panics
+/// Panics on the first unknown parameter.
+pub fn check_names(declared: &[&str]) {
+    OVERRIDES.with(|overrides| {
+        if let Err(e) = check_overrides(&overrides.borrow(), declared) {
+            panic!("{e}");
+        }
+    });
+}
+
+/// Parameter names of a puzzle without [`crate::params`]. `solution!` imports them, `params!` shadows them.
+#[doc(hidden)]
+pub mod defaults {
+    pub const __PARAMS: &[&str] = &[];
+}
+
+/// Declares the parameters of a puzzle with their default values and creates a `params()` function that returns their current values.
+///
+/// ```ignore
+/// advent_of_code::params! {
+///     /// Width and height of the grid.
+///     dim: i32 = 70,
+///     bytes: usize = 1024,
+/// }
+///
+/// fn part_one(input: &str) -> Option<u32> {
+///     let dim = params().dim;
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($( $(#[$meta:meta])* $name:ident: $ty:ty = $default:expr ),* $(,)?) => {
+        /// Parameters of this puzzle, see [`$crate::template::params`].
+        #[derive(Clone, Debug)]
+        #[allow(dead_code)]
+        struct Params {
+            $( $(#[$meta])* $name: $ty, )*
+        }
+
+        /// Names of the parameters, see [`$crate::template::params::defaults`].
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        const __PARAMS: &[&str] = &[$( stringify!($name) ),*];
+
+        /// The parameters of the current run: defaults, overridden by the example or by `--param <name>=<value>`.
+        fn params() -> Params {
+            $crate::template::params::check_names(__PARAMS);
+            Params {
+                $( $name: $crate::template::params::get(stringify!($name), $default), )*
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_names, check_overrides, get, parse_overrides, with_overrides};
+
+    #[test]
+    fn overrides_defaults() {
+        let overrides = parse_overrides(&["dim=6".into(), "bytes=12".into()]).unwrap();
+
+        assert_eq!(get("dim", 70), 70);
+        with_overrides(&overrides, || {
+            assert_eq!(get("dim", 70), 6);
+            assert_eq!(get("bytes", 1024_usize), 12);
+            assert_eq!(get("savings", 100), 100);
+            check_names(&["dim", "bytes"]);
+        });
+        assert_eq!(get("dim", 70), 70);
+    }
+
+    #[test]
+    fn rejects_malformed_overrides() {
+        assert!(parse_overrides(&["dim".into()]).is_err());
+        assert!(parse_overrides(&["=6".into()]).is_err());
+        assert_eq!(
+            parse_overrides(&["name=a=b".into()]).unwrap()["name"],
+            "a=b"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown parameter `size`")]
+    fn rejects_unknown_names() {
+        let overrides = parse_overrides(&["size=6".into()]).unwrap();
+        with_overrides(&overrides, || check_names(&["dim"]));
+    }
+
+    #[test]
+    fn rejects_overrides_without_parameters() {
+        let overrides = parse_overrides(&["dim=6".into()]).unwrap();
+        assert_eq!(
+            check_overrides(&overrides, &[]).unwrap_err(),
+            "unknown parameter `dim`, this puzzle has no parameters."
+        );
+        assert!(check_overrides(&overrides, &["dim"]).is_ok());
+    }
+}
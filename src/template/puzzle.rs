@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{Day, Year};
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2024/inputs/08.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    /// Path of the solution binary of this puzzle, e.g. `./src/bin/2024-08.rs`.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses `<year>-<day>` or `<year>/<day>`.
+/// A plain day number is resolved against the default year, see [`Year::from_env`].
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once(['-', '/']) {
+            Some((year, day)) => (year.parse().map_err(|_| PuzzleIdFromStrError::Year)?, day),
+            None => (
+                Year::from_env().ok_or(PuzzleIdFromStrError::MissingYear)?,
+                s,
+            ),
+        };
+
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError::Day)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub enum PuzzleIdFromStrError {
+    Year,
+    Day,
+    MissingYear,
+}
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleIdFromStrError::Year => f.write_str("expecting a year of 2015 or later"),
+            PuzzleIdFromStrError::Day => f.write_str("expecting a day number between 1 and 25"),
+            PuzzleIdFromStrError::MissingYear => f.write_str(
+                "no year given. Use `<year>-<day>` or set `AOC_YEAR` in \".cargo/config.toml\"",
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+
+    #[test]
+    fn parses_puzzle_ids() {
+        assert_eq!("2024-08".parse::<PuzzleId>().ok(), Some(puzzle!(2024, 8)));
+        assert_eq!("2023/1".parse::<PuzzleId>().ok(), Some(puzzle!(2023, 1)));
+        assert!("2024-26".parse::<PuzzleId>().is_err());
+        assert!("2014-01".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = puzzle!(2024, 8);
+        assert_eq!(puzzle.to_string(), "2024-08");
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2024/inputs/08.txt");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2024-08.rs");
+    }
+}
@@ -2,10 +2,16 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::stats::format_bytes;
+use crate::template::timings::{TimingStats, Timings};
+use crate::template::{PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Every year has its own table, enclosed by a pair of markers.
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
+
+fn get_marker(year: Year) -> String {
+    format!("{MARKER_PREFIX} {year} --->")
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,13 +31,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,55 +53,127 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_cell(time: Option<&str>, stats: Option<&TimingStats>) -> String {
+    let time = format!("`{}`", time.unwrap_or("-"));
+
+    match stats {
+        Some(stats) => format!(
+            "{time} <sub>median `{}` · σ `{}` · p95 `{}`</sub>",
+            stats.median, stats.std_dev, stats.p95
+        ),
+        None => time,
+    }
+}
+
+fn format_memory_cell(stats: Option<&TimingStats>) -> String {
+    match stats.and_then(|stats| stats.memory) {
+        Some(memory) => format!(
+            "`{}` <sub>total `{}` · {} allocations</sub>",
+            format_bytes(memory.peak_bytes),
+            format_bytes(memory.total_bytes),
+            memory.allocations
+        ),
+        None => "`-`".into(),
+    }
+}
+
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let marker = get_marker(year);
+    let header = format!("{prefix} Benchmarks {year}");
+
+    // NOTE: memory columns are only shown once heap usage was measured for any part.
+    let has_memory = timings.data.iter().any(|timing| {
+        [&timing.part_1_stats, &timing.part_2_stats]
+            .iter()
+            .any(|stats| stats.as_ref().is_some_and(|stats| stats.memory.is_some()))
+    });
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 peak memory | Part 2 peak memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let path = PuzzleId::new(year, timing.day).bin_path();
+        let mut line = format!(
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_cell(timing.part_1.as_deref(), timing.part_1_stats.as_ref()),
+            format_cell(timing.part_2.as_deref(), timing.part_2_stats.as_ref()),
+        );
+
+        if has_memory {
+            line.push_str(&format!(
+                " {} | {} |",
+                format_memory_cell(timing.part_1_stats.as_ref()),
+                format_memory_cell(timing.part_2_stats.as_ref()),
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Add empty markers for a year that does not have a table yet, right after the last table of another year.
+fn insert_markers(s: &mut String, year: Year) -> Result<(), Error> {
+    let pos = s
+        .rfind(MARKER_PREFIX)
+        .and_then(|start| Some(start + s[start..].find("-->")? + 3))
+        .ok_or_else(|| Error::Parser("Could not find any benchmarking table.".into()))?;
+
+    let marker = get_marker(year);
+    s.insert_str(pos, &format!("\n\n{marker}{marker}"));
+    Ok(())
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    if !s.contains(&get_marker(year)) {
+        insert_markers(s, year)?;
+    }
+
+    let positions = locate_table(s, &get_marker(year))?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{get_marker, update_content};
+    use crate::{
+        day,
+        template::{
+            stats::MemoryStats,
+            timings::{Timing, TimingStats, Timings},
+        },
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +182,24 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,50 +210,117 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2024));
+        let mut s = format!("{} {} {}", marker, marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## Benchmarks 2024"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn adds_tables_for_new_years() {
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(&get_marker(year!(2023))).count(), 2);
+        assert!(s.find("## Benchmarks 2024").unwrap() < s.find("## Benchmarks 2023").unwrap());
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) |"));
+        assert!(s.ends_with("\nbaz"));
+    }
+
+    #[test]
+    fn format_benchmark_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(TimingStats {
+            median: "9ms".into(),
+            min: "8ms".into(),
+            max: "20ms".into(),
+            std_dev: "1ms".into(),
+            p95: "15ms".into(),
+            samples: 100,
+            outliers: 1,
+            memory: None,
+        });
+
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), timings, 190.0).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2024-01.rs) | `10ms` <sub>median `9ms` · σ `1ms` · p95 `15ms`</sub> | `20ms` |"
+        ));
+        assert!(!s.contains("peak memory"));
+    }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(TimingStats {
+            median: "9ms".into(),
+            min: "8ms".into(),
+            max: "20ms".into(),
+            std_dev: "1ms".into(),
+            p95: "15ms".into(),
+            samples: 1,
+            outliers: 0,
+            memory: Some(MemoryStats {
+                total_bytes: 3 * 1024 * 1024,
+                peak_bytes: 1536,
+                allocations: 12,
+            }),
+        });
+
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 peak memory | Part 2 peak memory |"));
+        assert!(
+            s.contains("| `20ms` | `1.5 KiB` <sub>total `3.0 MiB` · 12 allocations</sub> | `-` |")
+        );
+        assert!(s.contains("| [Day 2](./src/bin/2024-02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
+
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2024));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## Benchmarks 2024",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2024 --->",
             "baz",
         ]
         .join("\n");
@@ -0,0 +1,368 @@
+/// Machine-readable records emitted by solution binaries with `--format json`.
+/// Every line of output is a JSON object describing the result of a single part.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::Day;
+
+/// Part number of the record that reports the parse step of a [`crate::template::Solution`].
+pub const PARSE_STEP: u8 = 0;
+
+/// Human-readable name of a part, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    match part {
+        PARSE_STEP => "Parse".into(),
+        part => format!("Part {part}"),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    /// The answer, `None` if the part returned `None` or failed.
+    pub answer: Option<String>,
+    pub stats: Option<BenchStats>,
+    /// Error message if the part panicked or did not finish.
+    pub error: Option<String>,
+    /// The limit that stopped the part, see [`crate::template::isolation`].
+    pub limit: Option<Limit>,
+}
+
+/// A resource limit that a part exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    Timeout,
+    OutOfMemory,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Limit::Timeout => "TIMEOUT",
+            Limit::OutOfMemory => "OOM",
+        })
+    }
+}
+
+impl FromStr for Limit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TIMEOUT" => Ok(Limit::Timeout),
+            "OOM" => Ok(Limit::OutOfMemory),
+            _ => Err(format!("unknown limit `{s}`.")),
+        }
+    }
+}
+
+impl PartRecord {
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part records only contain finite numbers")
+    }
+
+    /// Parse a line of output. Returns `None` for lines that are not records, e.g. debug output of a solution.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        PartRecord::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), optional_string(value.answer.as_ref()));
+        map.insert("error".into(), optional_string(value.error.as_ref()));
+        map.insert(
+            "limit".into(),
+            optional_string(value.limit.map(|l| l.to_string()).as_ref()),
+        );
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(stats) => JsonValue::from(stats),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_bytes: get_number("total_bytes")?,
+            peak_bytes: get_number("peak_bytes")?,
+            allocations: get_number("allocations")?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let error = json
+            .get("error")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.error to be null or string.")?;
+
+        // NOTE: `limit` is optional, records only carry it when a part was stopped.
+        let limit = match json.get("limit") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.limit to be null or string.")?
+                    .parse::<Limit>()?,
+            ),
+        };
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        Ok(PartRecord {
+            day,
+            part,
+            answer: answer.cloned(),
+            stats,
+            error: error.cloned(),
+            limit,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record.stats to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("Expected record.stats.{key} to be a number."))
+        };
+
+        let get_duration = |key: &str| get_number(key).map(|v| Duration::from_nanos(v as u64));
+
+        Ok(BenchStats {
+            mean: get_duration("mean_nanos")?,
+            median: get_duration("median_nanos")?,
+            min: get_duration("min_nanos")?,
+            max: get_duration("max_nanos")?,
+            std_dev: get_duration("std_dev_nanos")?,
+            p95: get_duration("p95_nanos")?,
+            samples: get_number("samples")? as u128,
+            outliers: get_number("outliers")? as usize,
+            // NOTE: memory is only present if it was measured.
+            memory: json.get("memory").map(MemoryStats::try_from).transpose()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Limit, PartRecord};
+    use crate::{
+        day,
+        template::stats::{BenchStats, MemoryStats},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(3),
+            part: 2,
+            answer: Some("line 1\n\"line 2\"".into()),
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(10),
+                Duration::from_nanos(20),
+                Duration::from_nanos(30),
+            ]),
+            error: None,
+            limit: None,
+        };
+
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_memory_stats() {
+        let mut stats = BenchStats::single(Duration::from_nanos(10));
+        stats.memory = Some(MemoryStats {
+            total_bytes: 1536,
+            peak_bytes: 1024,
+            allocations: 4,
+        });
+
+        let record = PartRecord {
+            day: day!(3),
+            part: 1,
+            answer: Some("42".into()),
+            stats: Some(stats),
+            error: None,
+            limit: None,
+        };
+
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_failed_records() {
+        let record = PartRecord {
+            day: day!(3),
+            part: 1,
+            answer: None,
+            stats: None,
+            error: Some("index out of bounds".into()),
+            limit: None,
+        };
+
+        assert_eq!(
+            PartRecord::from_json_line(&record.to_json_line()),
+            Some(record)
+        );
+    }
+
+    #[test]
+    fn roundtrips_limits() {
+        let record = PartRecord {
+            day: day!(20),
+            part: 1,
+            answer: None,
+            stats: None,
+            error: Some("exceeded timeout of 10s".into()),
+            limit: Some(Limit::Timeout),
+        };
+
+        assert_eq!(
+            PartRecord::from_json_line(&record.to_json_line()),
+            Some(record)
+        );
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartRecord::from_json_line("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(PartRecord::from_json_line("{ not json"), None);
+        assert_eq!(PartRecord::from_json_line(r#"{ "foo": 1 }"#), None);
+    }
+}
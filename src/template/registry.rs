@@ -0,0 +1,23 @@
+/// Solutions that are linked into the main binary.
+/// The registry itself is generated by `build.rs`, this module defines its entries.
+use crate::template::records::PartRecord;
+use crate::template::runner::InputSource;
+use crate::template::PuzzleId;
+
+/// Runs every part of a solution on an input and passes one record per part to the callback, as soon as the part finishes.
+/// The second argument controls whether parts are benched.
+pub type RunSolution = fn(InputSource, bool, &mut dyn FnMut(PartRecord));
+
+/// Entry point of a puzzle's solution that can be called without spawning a child process.
+#[derive(Clone, Copy)]
+pub struct DaySolution {
+    pub puzzle: PuzzleId,
+    /// The parts this solution implements. Solutions implementing [`crate::template::Solution`] also report their parse step.
+    pub parts: &'static [u8],
+    pub run: RunSolution,
+}
+
+/// Find the solution of a puzzle in a registry.
+pub fn find(solutions: &[DaySolution], puzzle: PuzzleId) -> Option<&DaySolution> {
+    solutions.iter().find(|s| s.puzzle == puzzle)
+}
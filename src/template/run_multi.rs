@@ -1,40 +1,196 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{self, Display},
+    fs, io, process,
+    sync::mpsc,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::{AnswerStatus, Answers},
+    counting_alloc,
+    isolation::{self, Limits},
+    records::{part_label, Limit, PartRecord, PARSE_STEP},
+    registry::{self, DaySolution},
+    runner::InputSource,
+    timings::{Timing, TimingStats, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// How solutions that are run as child processes are built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    /// With the `dhat-heap` feature, which measures the heap usage of every part.
+    Dhat,
+}
+
+impl Profile {
+    /// Arguments of `cargo run` that select this profile.
+    fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Debug => &[],
+            Profile::Release => &["--release"],
+            Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+        }
+    }
+}
+
+/// Outcome of running a set of days.
+pub struct RunSummary {
+    /// Benchmark timings, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Answers produced during this run.
+    pub answers: Answers,
+    /// Parts whose answer does not match the accepted answer.
+    pub regressions: Vec<(Day, u8)>,
+    /// Parts that were stopped because they exceeded a limit.
+    pub exceeded_limits: Vec<(Day, u8, Limit)>,
+}
+
+/// Run a set of days of a year. Days that are part of `solutions` are called in-process, all others are run as child processes.
+///
+/// With `jobs` set, days are run concurrently on a pool of that many workers (`0` uses one worker per CPU).
+/// The output of every day is buffered and printed in day order once the day and all days before it have finished.
+///
+/// With `limits` set, in-process solutions are run in a child process instead, which is stopped when a part exceeds a limit.
+/// Allocation counters are process-wide, so `jobs` is ignored for in-process solutions while [`counting_alloc`] is enabled.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    solutions: &[DaySolution],
+    profile: Profile,
+    is_timed: bool,
+    jobs: Option<usize>,
+    limits: Limits,
+) -> RunSummary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers = Answers::default();
+    let mut regressions = vec![];
+    let mut exceeded_limits = vec![];
+
+    // NOTE: without the accepted answers, regressions would go unnoticed.
+    let accepted_answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Invalid answers file {e}");
+        process::exit(1);
+    });
 
-    let mut need_space = false;
+    // NOTE: concurrent in-process days would add up their allocations.
+    let jobs =
+        jobs.filter(|_| solutions.is_empty() || limits.is_set() || !counting_alloc::is_enabled());
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let run_day = |index: usize, day: Day, output: &mut DayOutput| {
+        if index > 0 {
+            output.out("");
+        }
+
+        output.out(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        output.out("------");
+
+        let puzzle = PuzzleId::new(year, day);
+
+        match registry::find(solutions, puzzle) {
+            Some(solution) if limits.is_set() => {
+                Some(isolation::run_isolated(solution, is_timed, limits, output))
+            }
+            Some(solution) => Some(run_in_process(solution, is_timed, output)),
+            None => match child_commands::run_solution(puzzle, is_timed, profile, output) {
+                Ok(records) => records,
+                Err(e) => {
+                    // NOTE: keep going with the next day, the missing answers are checked like any other.
+                    output.err(format!("Failed to run day {day}: {e}"));
+                    Some(vec![])
+                }
+            },
+        }
+    };
+
+    let mut finish_day = |day: Day, records: Option<Vec<PartRecord>>| {
+        let Some(records) = records else {
+            println!("Not solved.");
+            return;
+        };
+
+        for record in &records {
+            if let Some(limit) = record.limit {
+                exceeded_limits.push((day, record.part, limit));
             }
-            need_space = true;
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        let mut checks = vec![];
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        for part in [1, 2] {
+            let answer = records
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.answer.clone());
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            if let Some(answer) = &answer {
+                answers.set(day, part, answer);
             }
-        });
 
-    if is_timed {
+            let status = accepted_answers.check(day, part, answer.as_deref());
+            if matches!(status, AnswerStatus::Fail { .. }) {
+                regressions.push((day, part));
+            }
+            checks.push(format!("part {part} {status}"));
+        }
+
+        println!("{ANSI_ITALIC}Check: {}{ANSI_RESET}", checks.join(", "));
+
+        if is_timed {
+            timings.push(timing_from_records(day, &records));
+        }
+    };
+
+    match jobs {
+        None => {
+            for (index, day) in days.iter().enumerate() {
+                let mut output = DayOutput::immediate();
+                let records = run_day(index, *day, &mut output);
+                finish_day(*day, records);
+            }
+        }
+        Some(jobs) => run_parallel(&days, jobs, run_day, |day, output, records| {
+            output.flush();
+            finish_day(day, records);
+        }),
+    }
+
+    if regressions.is_empty() {
+        println!("\n{ANSI_BOLD}Answers:{ANSI_RESET} no regressions.");
+    } else {
+        let parts: Vec<String> = regressions
+            .iter()
+            .map(|(day, part)| format!("day {day} part {part}"))
+            .collect();
+        println!(
+            "\n{ANSI_BOLD}Answers:{ANSI_RESET} {} regression(s) in {}.",
+            regressions.len(),
+            parts.join(", ")
+        );
+    }
+
+    if !exceeded_limits.is_empty() {
+        let parts: Vec<String> = exceeded_limits
+            .iter()
+            .map(|(day, part, limit)| {
+                format!("day {day} {} {limit}", part_label(*part).to_lowercase())
+            })
+            .collect();
+        println!(
+            "\n{ANSI_BOLD}Limits:{ANSI_RESET} {} part(s) exceeded a limit: {}.",
+            exceeded_limits.len(),
+            parts.join(", ")
+        );
+    }
+
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,7 +199,180 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    RunSummary {
+        timings,
+        answers,
+        regressions,
+        exceeded_limits,
+    }
+}
+
+/// Run `run` for every day on a bounded worker pool and hand the results to `finish` in day order.
+/// `finish` is called on the current thread as soon as a day and all days before it have completed.
+fn run_parallel<R: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(usize, Day, &mut DayOutput) -> R + Sync,
+    mut finish: impl FnMut(Day, DayOutput, R),
+) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .expect("could not create worker pool");
+
+    let (tx, rx) = mpsc::channel();
+    let run = &run;
+
+    // NOTE: `in_place_scope` keeps this closure on the current thread, so collecting results never blocks a worker.
+    pool.in_place_scope(|scope| {
+        for (index, day) in days.iter().enumerate() {
+            let tx = tx.clone();
+            scope.spawn(move |_| {
+                let mut output = DayOutput::buffered();
+                let result = run(index, *day, &mut output);
+                tx.send((index, output, result)).unwrap();
+            });
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+
+        for (index, output, result) in rx {
+            pending.insert(index, (output, result));
+
+            while let Some((output, result)) = pending.remove(&next_index) {
+                finish(days[next_index], output, result);
+                next_index += 1;
+            }
+        }
+    });
+}
+
+/// Output produced while running a single day.
+/// Sequential runs print lines immediately, parallel runs buffer them so that days do not interleave.
+pub struct DayOutput {
+    buffer: Option<Vec<OutputLine>>,
+}
+
+enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl DayOutput {
+    pub fn immediate() -> Self {
+        Self { buffer: None }
+    }
+
+    pub fn buffered() -> Self {
+        Self {
+            buffer: Some(vec![]),
+        }
+    }
+
+    pub fn is_buffered(&self) -> bool {
+        self.buffer.is_some()
+    }
+
+    pub fn out(&mut self, line: impl Into<String>) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push(OutputLine::Stdout(line.into())),
+            None => println!("{}", line.into()),
+        }
+    }
+
+    pub fn err(&mut self, line: impl Into<String>) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.push(OutputLine::Stderr(line.into())),
+            None => eprintln!("{}", line.into()),
+        }
+    }
+
+    /// Print all buffered lines.
+    pub fn flush(self) {
+        for line in self.buffer.unwrap_or_default() {
+            match line {
+                OutputLine::Stdout(line) => println!("{line}"),
+                OutputLine::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+}
+
+/// Run a solution that is linked into this binary and write its records to `output`.
+fn run_in_process(
+    solution: &DaySolution,
+    is_timed: bool,
+    output: &mut DayOutput,
+) -> Vec<PartRecord> {
+    let input_path = solution.puzzle.data_path("inputs", "txt");
+
+    let Ok(input) = fs::read_to_string(&input_path) else {
+        output.err(format!("Could not open input file \"{input_path}\"."));
+        return vec![];
+    };
+
+    let source = InputSource {
+        path: &input_path,
+        text: &input,
+    };
+
+    let mut records = vec![];
+    (solution.run)(source, is_timed, &mut |record| {
+        output.out(child_commands::format_record(&record));
+        records.push(record);
+    });
+    records
+}
+
+/// Summarize the benchmark results of all parts of a day.
+/// Parts that exceeded a limit are stored as `TIMEOUT` or `OOM` instead of a duration.
+/// The parse step of a [`crate::template::Solution`] only counts towards the total.
+fn timing_from_records(day: Day, records: &[PartRecord]) -> Timing {
+    let mut timing = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        total_nanos: 0_f64,
+    };
+
+    for record in records {
+        if let Some(limit) = record.limit {
+            match record.part {
+                1 => timing.part_1 = Some(limit.to_string()),
+                2 => timing.part_2 = Some(limit.to_string()),
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some(stats) = &record.stats else {
+            continue;
+        };
+
+        let mean = Some(format!("{:.1?}", stats.mean));
+        let stats_summary = Some(TimingStats::from(stats));
+
+        match record.part {
+            PARSE_STEP => {}
+            1 => (timing.part_1, timing.part_1_stats) = (mean, stats_summary),
+            2 => (timing.part_2, timing.part_2_stats) = (mean, stats_summary),
+            _ => continue,
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += stats.mean.as_nanos() as f64;
+        }
     }
+
+    timing
 }
 
 #[allow(dead_code)]
@@ -53,22 +382,29 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "broken pipe"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayOutput, Error, Profile};
+    use crate::template::counting_alloc;
+    use crate::template::records::{part_label, PartRecord, PARSE_STEP};
+    use crate::template::runner::format_duration;
+    use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,28 +412,38 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle and collect its records.
+    /// Records are written to `output` in human-readable form as they arrive, other output is forwarded as-is.
+    /// Returns `None` if the day has not been scaffolded yet.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        profile: Profile,
+        output: &mut DayOutput,
+    ) -> Result<Option<Vec<PartRecord>>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+        args.extend(profile.cargo_args());
 
-        if is_release {
-            args.push("--release");
+        if counting_alloc::is_enabled() && profile != Profile::Dhat {
+            args.extend(["--features", counting_alloc::FEATURE]);
         }
 
+        args.extend(["--", "--format", "json"]);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing records.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,150 +454,180 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut records = vec![];
 
+        // NOTE: stderr is read on a separate thread and either forwarded right away or collected for buffered output.
+        let is_buffered = output.is_buffered();
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut lines = vec![];
+            for line in stderr.lines() {
+                let line = line.unwrap();
+                if is_buffered {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
+            }
+            lines
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartRecord::from_json_line(&line) {
+                Some(record) => {
+                    output.out(format_record(&record));
+                    records.push(record);
+                }
+                None => output.out(line),
+            }
         }
 
-        thread.join().unwrap();
+        for line in thread.join().unwrap() {
+            output.err(line);
+        }
         cmd.wait()?;
 
-        Ok(output)
-    }
-
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
+        Ok(Some(records))
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Format a record the same way the solution binary would print it without `--format json`.
+    pub fn format_record(record: &PartRecord) -> String {
+        let part_str = part_label(record.part);
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+        match (&record.error, &record.stats, &record.answer) {
+            (None, Some(stats), None) if record.part == PARSE_STEP => {
+                format!("{part_str}:{}", format_duration(stats))
+            }
+            (None, Some(stats), Some(answer)) if answer.contains('\n') => {
+                format!("{part_str}: ▼ {}\n{answer}", format_duration(stats))
+            }
+            (None, Some(stats), Some(answer)) => format!(
+                "{part_str}: {ANSI_BOLD}{answer}{ANSI_RESET}{}",
+                format_duration(stats)
+            ),
+            (None, Some(_), None) => format!("{part_str}: ✖"),
+            (error, _, _) if record.limit.is_some() => format!(
+                "{part_str}: ✖ {ANSI_BOLD}{}{ANSI_RESET} ({})",
+                record.limit.unwrap(),
+                error.as_deref().unwrap_or("no result")
+            ),
+            (Some(error), _, _) if error.contains('\n') => {
+                format!("{part_str}: ✖\n{}", error.trim_matches('\n'))
+            }
+            (error, _, _) => format!(
+                "{part_str}: ✖ ({})",
+                error.as_deref().unwrap_or("no result")
+            ),
+        }
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::super::timing_from_records;
+        use super::format_record;
+        use crate::{
+            day,
+            template::{
+                records::{Limit, PartRecord, PARSE_STEP},
+                stats::BenchStats,
+            },
+        };
+        use std::time::Duration;
+
+        fn record(part: u8, nanos: &[u64]) -> PartRecord {
+            let samples: Vec<Duration> = nanos.iter().map(|x| Duration::from_nanos(*x)).collect();
+            PartRecord {
+                day: day!(1),
+                part,
+                answer: Some("42".into()),
+                stats: BenchStats::from_samples(&samples),
+                error: None,
+                limit: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn summarizes_records() {
+            let res = timing_from_records(
                 day!(1),
+                &[record(1, &[70, 74, 78]), record(2, &[74_130_000; 5])],
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+
+            let stats = res.part_1_stats.unwrap();
+            assert_eq!(stats.median, "74.0ns");
+            assert_eq!(stats.min, "70.0ns");
+            assert_eq!(stats.max, "78.0ns");
+            assert_eq!(stats.samples, 3);
+            assert_eq!(res.part_2_stats.unwrap().samples, 5);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn summarizes_missing_parts() {
+            let mut failed = record(2, &[]);
+            failed.error = Some("panicked".into());
+
+            let res = timing_from_records(day!(1), &[failed]);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn summarizes_parse_step() {
+            let mut parse = record(PARSE_STEP, &[1_000]);
+            parse.answer = None;
+
+            let res = timing_from_records(day!(5), &[parse.clone(), record(1, &[70])]);
+            assert_eq!(res.total_nanos, 1_070_f64);
+            assert_eq!(res.part_1.as_deref(), Some("70.0ns"));
+            assert!(res.part_2.is_none());
+
+            assert_eq!(format_record(&parse), "Parse: (1.0µs)");
+        }
+
+        #[test]
+        fn summarizes_exceeded_limits() {
+            let mut timed_out = record(1, &[]);
+            timed_out.error = Some("exceeded timeout of 10.0s".into());
+            timed_out.limit = Some(Limit::Timeout);
+
+            let mut out_of_memory = record(2, &[]);
+            out_of_memory.limit = Some(Limit::OutOfMemory);
+
+            let res = timing_from_records(day!(20), &[timed_out.clone(), out_of_memory]);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.as_deref(), Some("TIMEOUT"));
+            assert_eq!(res.part_2.as_deref(), Some("OOM"));
+            assert!(res.part_1_stats.is_none());
+
+            assert_eq!(
+                format_record(&timed_out),
+                "Part 1: ✖ \u{1b}[1mTIMEOUT\u{1b}[0m (exceeded timeout of 10.0s)"
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn formats_records() {
+            let res = format_record(&record(1, &[74]));
+            assert_eq!(res, "Part 1: \u{1b}[1m42\u{1b}[0m (74.0ns)");
+
+            let mut multiline = record(2, &[74]);
+            multiline.answer = Some("#.\n.#".into());
+            assert_eq!(format_record(&multiline), "Part 2: ▼  (74.0ns)\n#.\n.#");
+
+            let mut failed = record(2, &[]);
+            failed.error = Some("panicked".into());
+            assert_eq!(format_record(&failed), "Part 2: ✖ (panicked)");
+
+            let mut report = record(1, &[]);
+            report.error = Some("\n  × unexpected symbol\n  ╰── here\n".into());
+            assert_eq!(
+                format_record(&report),
+                "Part 1: ✖\n  × unexpected symbol\n  ╰── here"
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
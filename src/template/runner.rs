@@ -1,64 +1,524 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use miette::{NamedSource, Report};
+
+use crate::template::answers::{AnswerStatus, Answers};
+use crate::template::counting_alloc;
+use crate::template::examples::{Examples, DEFAULT_EXAMPLE};
+use crate::template::ledger::{Ledger, Verdict};
+use crate::template::records::{PartRecord, PARSE_STEP};
+use crate::template::registry::RunSolution;
+use crate::template::run_multi::child_commands::format_record;
+use crate::template::stats::{format_bytes, BenchStats, MemoryStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_client, params, IntoAnswer, PuzzleId, Solution, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// The input text a solution runs on and the file it was read from.
+/// Errors returned by solutions are rendered as reports that point into this text.
+#[derive(Clone, Copy)]
+pub struct InputSource<'a> {
+    pub path: &'a str,
+    pub text: &'a str,
+}
+
+impl InputSource<'_> {
+    /// Attach the input to a report, so that labels of the error are shown in context.
+    fn annotate(&self, report: Report) -> Report {
+        report.with_source_code(NamedSource::new(self.path, self.text.to_string()))
+    }
+}
+
+/// Outcome of a single part: the answer if there is one, or the error returned by the part.
+type PartResult = Result<Option<String>, Report>;
+
+pub fn run_part<I: Copy, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    source: InputSource,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    if is_json_output() {
+        run_part_json(func, input, source, puzzle, part);
+        return;
+    }
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(
+        func,
+        input,
+        |result| {
+            let result = result.into_answer().map_err(|e| source.annotate(e));
+            print_result(&result, &part_str, "");
+
+            if is_timed() {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+
+            result
+        },
+        is_timed(),
+    );
+
+    print_result(&result, &part_str, &format_duration(&stats));
+
+    if let Ok(Some(result)) = result {
+        submit_result(result, puzzle, part);
+    }
+}
+
+/// Run a [`Solution`]: parse the input once, then run both parts, or only `part`, on the parsed value.
+/// Parsing is timed separately and printed before the parts. If parsing fails, the parts are not run.
+pub fn run_solution<S: Solution>(source: InputSource, puzzle: PuzzleId, part: Option<u8>) {
+    let is_selected = |p: u8| part.is_none_or(|part| part == p);
+
+    if is_json_output() {
+        run_solution_records::<S>(source, puzzle, part, is_timed(), &mut |record| {
+            println!("{}", record.to_json_line());
+        });
+        return;
+    }
+
+    let (parsed, stats) = run_timed(
+        S::parse,
+        source.text,
+        |parsed| {
+            if is_timed() && parsed.is_ok() {
+                print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+            parsed.map_err(|e| source.annotate(e))
+        },
+        is_timed(),
+    );
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(report) => {
+            println!("Parse: ✖");
+            println!("{report:?}");
+            return;
+        }
+    };
+
+    print!("\r");
+    println!("Parse:{}", format_duration(&stats));
+
+    if is_selected(1) {
+        run_part(S::part_one, &parsed, source, puzzle, 1);
+    }
+    if is_selected(2) {
+        run_part(S::part_two, &parsed, source, puzzle, 2);
+    }
+}
+
+/// Run a [`Solution`] without printing anything and pass one record for the parse step and each part to `on_record`.
+/// Only `part` runs and is reported if given. If parsing fails or panics, the parts are reported as not run.
+pub fn run_solution_records<S: Solution>(
+    source: InputSource,
+    puzzle: PuzzleId,
+    part: Option<u8>,
+    is_timed: bool,
+    on_record: &mut dyn FnMut(PartRecord),
+) {
+    let is_selected = |p: u8| part.is_none_or(|part| part == p);
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(S::parse, source.text, |parsed| parsed, is_timed)
+    }));
+
+    let error = match outcome {
+        Ok((Ok(parsed), stats)) => {
+            on_record(PartRecord {
+                day: puzzle.day,
+                part: PARSE_STEP,
+                answer: None,
+                stats: Some(stats),
+                error: None,
+                limit: None,
+            });
+
+            if is_selected(1) {
+                on_record(run_part_record(
+                    S::part_one,
+                    &parsed,
+                    source,
+                    puzzle,
+                    1,
+                    is_timed,
+                ));
+            }
+            if is_selected(2) {
+                on_record(run_part_record(
+                    S::part_two,
+                    &parsed,
+                    source,
+                    puzzle,
+                    2,
+                    is_timed,
+                ));
+            }
+            return;
+        }
+        Ok((Err(report), _)) => format!("{:?}", source.annotate(report)),
+        Err(e) => panic_message(&e),
+    };
+
+    let records = [
+        (PARSE_STEP, error),
+        (1, "not run".into()),
+        (2, "not run".into()),
+    ];
+    for (part, error) in records
+        .into_iter()
+        .filter(|&(p, _)| p == PARSE_STEP || is_selected(p))
+    {
+        on_record(PartRecord {
+            day: puzzle.day,
+            part,
+            answer: None,
+            stats: None,
+            error: Some(error),
+            limit: None,
+        });
+    }
+}
+
+/// Run a solution part and print its outcome as a single JSON record.
+fn run_part_json<I: Copy, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    source: InputSource,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let record = run_part_record(func, input, source, puzzle, part, is_timed());
+    println!("{}", record.to_json_line());
+}
+
+/// Run a solution part without printing anything and return its outcome as a record.
+/// Panics and errors are caught and reported in the record, errors are rendered as a report.
+pub fn run_part_record<I: Copy, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    source: InputSource,
+    puzzle: PuzzleId,
+    part: u8,
+    is_timed: bool,
+) -> PartRecord {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, IntoAnswer::into_answer, is_timed)
+    }));
+
+    let mut record = PartRecord {
+        day: puzzle.day,
+        part,
+        answer: None,
+        stats: None,
+        error: None,
+        limit: None,
+    };
+
+    match outcome {
+        Ok((Ok(answer), stats)) => (record.answer, record.stats) = (answer, Some(stats)),
+        Ok((Err(report), _)) => record.error = Some(format!("{:?}", source.annotate(report))),
+        Err(e) => record.error = Some(panic_message(&e)),
+    }
+
+    record
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "solution panicked".into())
+}
+
+/// Run a solution on every example of a puzzle and compare the answers with the expected answers.
+/// Parameters passed with `--param` take precedence over the parameters of the examples.
+/// Exits with a non-zero status if an answer does not match or an example could not be run.
+pub fn run_examples(puzzle: PuzzleId, declared: &[&str], run: RunSolution) {
+    let examples = match Examples::read_from_file(puzzle) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!(
+                "Invalid examples manifest \"{}\": {e}",
+                puzzle.data_path("examples", "json")
+            );
+            process::exit(1);
+        }
+    };
+
+    let overrides = param_args(declared);
+    let mut failed = vec![];
+
+    for (index, example) in examples.data.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        println!(
+            "{ANSI_BOLD}Example {}{ANSI_RESET} ({})",
+            example.name,
+            example.path(puzzle)
+        );
+
+        let records = match example.run(puzzle, run, &overrides) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("{e}");
+                failed.push(example.name.as_str());
+                continue;
+            }
+        };
+
+        records
+            .iter()
+            .for_each(|record| println!("{}", format_record(record)));
+
+        let checks = example.check(&records);
+        if checks
+            .iter()
+            .any(|(_, status)| matches!(status, AnswerStatus::Fail { .. }))
+        {
+            failed.push(example.name.as_str());
+        }
+
+        let checks: Vec<String> = checks
+            .iter()
+            .map(|(part, status)| format!("part {part} {status}"))
+            .collect();
+        println!("{ANSI_ITALIC}Check: {}{ANSI_RESET}", checks.join(", "));
+    }
+
+    let passed = examples.data.len() - failed.len();
+
+    if failed.is_empty() {
+        println!("\n{ANSI_BOLD}Examples:{ANSI_RESET} {passed} passed.");
+    } else {
+        println!(
+            "\n{ANSI_BOLD}Examples:{ANSI_RESET} {passed} passed, {} failed ({}).",
+            failed.len(),
+            failed.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+/// Parameter overrides passed as `--param <name>=<value>`, see [`crate::template::params`]. Exits if a name is not in `declared`.
+pub fn param_args(declared: &[&str]) -> HashMap<String, String> {
+    let args: Vec<String> = env::args().collect();
+    let pairs: Vec<String> = args
+        .windows(2)
+        .filter(|w| w[0] == "--param")
+        .map(|w| w[1].clone())
+        .collect();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    params::parse_overrides(&pairs)
+        .and_then(|overrides| params::check_overrides(&overrides, declared).map(|_| overrides))
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid parameter: {e}");
+            process::exit(1);
+        })
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// The input a solution runs on, selected with command line arguments:
+///
+/// - `--input <path>`: a file, e.g. the input of a colleague. `-` reads the input from stdin.
+/// - `--example [name]`: an example of the puzzle, `default` if no name is given. The parameters of the example apply.
+/// - otherwise the puzzle input, e.g. `data/2024/inputs/01.txt`.
+pub struct SelectedInput {
+    pub path: String,
+    pub text: String,
+    /// Parameter overrides of the example and of `--param`, the latter taking precedence.
+    pub params: HashMap<String, String>,
+}
+
+impl SelectedInput {
+    pub fn source(&self) -> InputSource<'_> {
+        InputSource {
+            path: &self.path,
+            text: &self.text,
+        }
     }
 }
 
+/// Read the input selected by `--input` or `--example`. Exits if the input can not be read.
+pub fn input_args(puzzle: PuzzleId, declared: &[&str]) -> SelectedInput {
+    let args: Vec<String> = env::args().collect();
+    let value_of = |flag: &str| {
+        let index = args.iter().position(|x| x == flag)?;
+        Some(
+            args.get(index + 1)
+                .filter(|x| !x.starts_with("--"))
+                .cloned(),
+        )
+    };
+
+    let overrides = param_args(declared);
+    let mut params = HashMap::new();
+
+    let (path, text) = match (value_of("--input"), value_of("--example")) {
+        (Some(Some(path)), _) if path == "-" => {
+            let mut text = String::new();
+            let read = stdin().read_to_string(&mut text).map(|_| text);
+            ("<stdin>".to_string(), read)
+        }
+        (Some(Some(path)), _) => {
+            let read = fs::read_to_string(&path);
+            (path, read)
+        }
+        (Some(None), _) => {
+            eprintln!("Missing value for `--input`. Format: --input <path>");
+            process::exit(1);
+        }
+        (None, Some(name)) => {
+            let name = name.unwrap_or_else(|| DEFAULT_EXAMPLE.into());
+            let example = Examples::read_from_file(puzzle)
+                .ok()
+                .and_then(|examples| examples.get(&name).cloned())
+                .unwrap_or_else(|| {
+                    eprintln!("Example `{name}` is not listed for {puzzle}.");
+                    process::exit(1);
+                });
+            params.clone_from(&example.params);
+
+            let path = example.path(puzzle);
+            let read = fs::read_to_string(&path);
+            (path, read)
+        }
+        (None, None) => {
+            let path = puzzle.data_path("inputs", "txt");
+            let read = fs::read_to_string(&path);
+            (path, read)
+        }
+    };
+
+    let text = text.unwrap_or_else(|e| {
+        eprintln!("Could not read input \"{path}\": {e}");
+        process::exit(1);
+    });
+
+    params.extend(overrides);
+    SelectedInput { path, text, params }
+}
+
+/// Whether `--input` or `--example` selected an input other than the puzzle input.
+fn is_alternative_input() -> bool {
+    env::args().any(|x| x == "--input" || x == "--example")
+}
+
+/// The part selected with `--part <part>`, `None` to run all parts. Exits if `parts` does not contain the part.
+pub fn part_arg(parts: &[u8]) -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")?;
+
+    match args.get(index + 1).and_then(|x| x.parse::<u8>().ok()) {
+        Some(part) if part != PARSE_STEP && parts.contains(&part) => Some(part),
+        Some(part) => {
+            eprintln!("Part {part} is not part of this solution.");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: --part <1|2>");
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether `--examples` was passed, which runs the solution on its examples instead of the puzzle input.
+pub fn is_examples_run() -> bool {
+    env::args().any(|x| x == "--examples")
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// Whether `--format json` was passed, which switches the output to JSON records.
+fn is_json_output() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .any(|w| w[0] == "--format" && w[1] == "json")
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
+///
+/// `hook` receives the result of the first execution before benching starts, its return value is returned alongside the stats.
+/// Heap usage is only measured for this first, cold execution, the benchmark samples are not measured.
+fn run_timed<I: Copy, T, U>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl FnOnce(T) -> U,
+    is_timed: bool,
+) -> (U, BenchStats) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
+        let measurement = counting_alloc::start();
 
-        func(input)
+        let result = func(input);
+        (result, heap_stats(measurement.as_ref()))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let mut stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
+    stats.memory = memory;
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Heap usage since the profiler was started. Only measured with the `dhat-heap` or the `count-allocations` feature.
+#[cfg(feature = "dhat-heap")]
+fn heap_stats(_: Option<&counting_alloc::Measurement>) -> Option<MemoryStats> {
+    let stats = dhat::HeapStats::get();
+    Some(MemoryStats {
+        total_bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
+        allocations: stats.total_blocks,
+    })
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_stats(measurement: Option<&counting_alloc::Measurement>) -> Option<MemoryStats> {
+    measurement.map(counting_alloc::Measurement::stats)
+}
 
+/// Bench a solution part. A number of warmup iterations is executed and discarded before samples are recorded.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // NOTE: warm up caches and branch predictors with ~10% of the bench iterations, but at least one.
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,35 +527,54 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).unwrap_or_else(|| BenchStats::single(*base_time))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+/// Format the timing of a part, e.g. `(74.1ns @ 100 samples) [median 70.0ns, min 60.0ns, max 2.0µs, σ 5.0ns, p95 80.0ns, 3 outliers]`.
+/// The heap usage is appended if it was measured, see [`format_memory`].
+pub fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        mean,
+        median,
+        min,
+        max,
+        std_dev,
+        p95,
+        samples,
+        outliers,
+        memory,
+    } = stats;
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+    let duration = if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({mean:.1?} @ {samples} samples) [median {median:.1?}, min {min:.1?}, max {max:.1?}, σ {std_dev:.1?}, p95 {p95:.1?}, {outliers} outliers]"
+        )
+    };
+
+    match memory {
+        Some(memory) => format!("{duration} {}", format_memory(memory)),
+        None => duration,
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Short summary of the heap usage of a part, e.g. `[heap: 1.5 KiB allocated, 1.0 KiB peak, 4 allocations]`.
+pub fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        "[heap: {} allocated, {} peak, {} allocations]",
+        format_bytes(memory.total_bytes),
+        format_bytes(memory.peak_bytes),
+        memory.allocations
+    )
+}
+
+fn print_result(result: &PartResult, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -114,25 +593,34 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖             ");
+            }
+        }
+        Err(report) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
                 print!("\r");
                 println!("{part}: ✖             ");
+                println!("{report:?}");
             }
         }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  1. `--submit <part>` matches `part`.
+///  2. the ledger of earlier submissions does not rule out the answer.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<(), aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +643,51 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if is_alternative_input() {
+        eprintln!("Not submitting: the answer was not computed on the puzzle input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+    let mut ledger = match Ledger::read_from_file(puzzle) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Not submitting: invalid submission ledger {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = ledger.check(part, &answer) {
+        eprintln!("Not submitting: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_client::submit(puzzle, part, &answer);
+
+    match &submission {
+        Ok(()) => println!("🎄 That's the right answer!"),
+        Err(e) => eprintln!("Submission failed: {e}"),
+    }
+
+    if let Some(verdict) = Verdict::from_submission(&submission) {
+        ledger.record(part, &answer, verdict);
+        if let Err(e) = ledger.store_file() {
+            eprintln!("Failed to store submission: {e}");
+        }
+    }
+
+    if submission.is_ok() {
+        match Answers::read_from_file(puzzle.year) {
+            Ok(mut answers) => {
+                answers.set(puzzle.day, part, &answer);
+                if let Err(e) = answers.store_file(puzzle.year) {
+                    eprintln!("Failed to store answer: {e}");
+                }
+            }
+            Err(e) => eprintln!("Failed to store answer: invalid answers file {e}"),
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(submission)
 }
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use miette::Report;
+
+/// A solution that parses its input once and runs both parts on the parsed value.
+///
+/// The runner times the parse step and each part separately. Use it with `solution!(<year>, <day>, <type>)`:
+///
+/// ```ignore
+/// use advent_of_code::template::Solution;
+///
+/// advent_of_code::solution!(2024, 1, Day01);
+///
+/// struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed<'a> = Vec<&'a str>;
+///     type PartOne = Option<usize>;
+///     type PartTwo = Option<usize>;
+///
+///     fn parse(input: &str) -> miette::Result<Self::Parsed<'_>> {
+///         Ok(input.lines().collect())
+///     }
+///
+///     fn part_one(lines: &Self::Parsed<'_>) -> Self::PartOne {
+///         Some(lines.len())
+///     }
+///
+///     fn part_two(_lines: &Self::Parsed<'_>) -> Self::PartTwo {
+///         None
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed input. May borrow from the input string.
+    type Parsed<'a>;
+    type PartOne: IntoAnswer;
+    type PartTwo: IntoAnswer;
+
+    /// Parse the input. Errors are reported with the location in the input file if they carry a label.
+    fn parse(input: &str) -> miette::Result<Self::Parsed<'_>>;
+    fn part_one(parsed: &Self::Parsed<'_>) -> Self::PartOne;
+    fn part_two(parsed: &Self::Parsed<'_>) -> Self::PartTwo;
+}
+
+/// Values that solution parts can return.
+///
+/// - `Option<T>`: `None` means that the part has no answer (yet).
+/// - `Result<T, E>`: `E` is any [`miette::Diagnostic`] or a [`Report`]. Labels of the error point at the input file.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Option<String>, Report>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<Option<String>, Report> {
+        Ok(self.map(|x| x.to_string()))
+    }
+}
+
+impl<T: Display, E: Into<Report>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Option<String>, Report> {
+        self.map(|x| Some(x.to_string())).map_err(Into::into)
+    }
+}
@@ -0,0 +1,164 @@
+/// Summary statistics for benchmark samples.
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub samples: u128,
+    /// Number of samples outside of the Tukey fences (1.5 IQR below the first or above the third quartile).
+    pub outliers: usize,
+    /// Heap usage of the first execution, if it was measured.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Heap usage of a single execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated in total.
+    pub total_bytes: u64,
+    /// Maximum number of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations.
+    pub allocations: u64,
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl BenchStats {
+    /// Statistics for a single, unbenched execution.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+            samples: 1,
+            outliers: 0,
+            memory: None,
+        }
+    }
+
+    /// Compute statistics from a list of samples. Returns `None` if `samples` is empty.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&nanos, 0.5)),
+            min: from_nanos(nanos[0]),
+            max: from_nanos(nanos[nanos.len() - 1]),
+            std_dev: from_nanos(variance.sqrt()),
+            p95: from_nanos(percentile(&nanos, 0.95)),
+            samples: samples.len() as u128,
+            outliers: nanos
+                .iter()
+                .filter(|x| **x < low_fence || **x > high_fence)
+                .count(),
+            memory: None,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of sorted values, `p` in range 0 to 1.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, BenchStats};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats =
+            BenchStats::from_samples(&nanos(&[10, 12, 11, 13, 14, 10, 12, 11, 13, 100])).unwrap();
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.mean, Duration::from_nanos(21));
+        assert_eq!(stats.median, Duration::from_nanos(12));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::from_nanos(28));
+        assert_eq!(stats.p95, Duration::from_nanos(61));
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[10])).unwrap();
+        assert_eq!(stats, BenchStats::single(Duration::from_nanos(10)));
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
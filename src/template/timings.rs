@@ -1,9 +1,14 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::records::Limit;
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::{Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Timings are stored per year, e.g. `./data/2024/timings.json`.
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/timings.json")
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,9 +16,61 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<TimingStats>,
+    pub part_2_stats: Option<TimingStats>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Returns the formatted mean duration of a part.
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Replace parts that exceeded a limit with the duration of `stored`, if it has one.
+    fn keep_durations_of(&self, stored: &Timing) -> Timing {
+        let mut timing = self.clone();
+
+        for part in [1, 2] {
+            let exceeded_limit = self.part(part).is_some_and(|s| s.parse::<Limit>().is_ok());
+            let stored_nanos = stored.part(part).and_then(parse_duration);
+            let (true, Some(nanos)) = (exceeded_limit, stored_nanos) else {
+                continue;
+            };
+
+            if part == 1 {
+                timing.part_1.clone_from(&stored.part_1);
+                timing.part_1_stats.clone_from(&stored.part_1_stats);
+            } else {
+                timing.part_2.clone_from(&stored.part_2);
+                timing.part_2_stats.clone_from(&stored.part_2_stats);
+            }
+            timing.total_nanos += nanos;
+        }
+
+        timing
+    }
+}
+
+/// Represents the distribution of benchmark samples for a single part.
+/// Durations are formatted the same way as `Timing::part_1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingStats {
+    pub median: String,
+    pub min: String,
+    pub max: String,
+    pub std_dev: String,
+    pub p95: String,
+    pub samples: u64,
+    pub outliers: u64,
+    /// Heap usage of the part, if it was measured.
+    pub memory: Option<MemoryStats>,
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,27 +79,32 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(format!("./data/{year}"))?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Parts of `other` that exceeded a limit do not overwrite a stored duration.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            data.push(match self.get(timing.day) {
+                Some(stored) => timing.keep_durations_of(stored),
+                None => timing.clone(),
+            });
         }
 
         for timing in &self.data {
@@ -51,22 +113,44 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether both parts of a day have a timing. Parts that exceeded a limit do not count as timed.
     pub fn is_day_complete(&self, day: Day) -> bool {
+        let is_timed = |part: &Option<String>| part.as_deref().and_then(parse_duration).is_some();
+
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && is_timed(&t.part_1) && is_timed(&t.part_2))
+    }
+}
+
+/// Parse a formatted duration such as `74.13ns` or `1.2s` to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -133,6 +217,14 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(stats) = &value.part_1_stats {
+            map.insert("part_1_stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = &value.part_2_stats {
+            map.insert("part_2_stats".into(), JsonValue::from(stats));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,15 +258,102 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats are optional to stay compatible with timings stored before they were introduced.
+        let part_1_stats = json
+            .get("part_1_stats")
+            .map(TimingStats::try_from)
+            .transpose()?;
+
+        let part_2_stats = json
+            .get("part_2_stats")
+            .map(TimingStats::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
             total_nanos,
         })
     }
 }
 
+impl From<&BenchStats> for TimingStats {
+    fn from(value: &BenchStats) -> Self {
+        TimingStats {
+            median: format!("{:.1?}", value.median),
+            min: format!("{:.1?}", value.min),
+            max: format!("{:.1?}", value.max),
+            std_dev: format!("{:.1?}", value.std_dev),
+            p95: format!("{:.1?}", value.p95),
+            samples: u64::try_from(value.samples).unwrap_or(u64::MAX),
+            outliers: value.outliers as u64,
+            memory: value.memory,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&TimingStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &TimingStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("median".into(), JsonValue::String(value.median.clone()));
+        map.insert("min".into(), JsonValue::String(value.min.clone()));
+        map.insert("max".into(), JsonValue::String(value.max.clone()));
+        map.insert("std_dev".into(), JsonValue::String(value.std_dev.clone()));
+        map.insert("p95".into(), JsonValue::String(value.p95.clone()));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected timing stats.{key} to be a string."))
+        };
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        Ok(TimingStats {
+            median: get_string("median")?,
+            min: get_string("min")?,
+            max: get_string("max")?,
+            std_dev: get_string("std_dev")?,
+            p95: get_string("p95")?,
+            samples: get_number("samples")?,
+            outliers: get_number("outliers")?,
+            memory: json.get("memory").map(MemoryStats::try_from).transpose()?,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -190,18 +369,24 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -223,6 +408,20 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "part_1_stats": { "median": "0.9ms", "min": "0.8ms", "max": "2ms", "std_dev": "0.1ms", "p95": "1.5ms", "samples": 100, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median, "0.9ms");
+            assert_eq!(stats.p95, "1.5ms");
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(stats.memory, None);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -281,11 +480,13 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +496,13 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,11 +512,13 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -333,6 +538,8 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -353,6 +560,8 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -365,6 +574,27 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_durations_of_parts_that_exceeded_a_limit() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: Some("35ms".into()),
+                    part_2: Some("TIMEOUT".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    total_nanos: 3.5e+7,
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[1].part_1, Some("35ms".into()));
+            assert_eq!(merged.data[1].part_2, Some("40ms".into()));
+            assert_eq!(merged.data[1].total_nanos, 7.5e+7);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,172 @@
+//! Compares benchmark timings against a stored baseline.
+use std::time::Duration;
+
+use crate::template::records::Limit;
+use crate::template::timings::{parse_duration, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Change of the mean duration of a single part relative to the baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    /// `None` if the part has no duration in the current run.
+    pub current_nanos: Option<f64>,
+    /// The limit the part exceeded in the current run, if any.
+    pub current_limit: Option<Limit>,
+}
+
+impl PartDelta {
+    /// Relative change in percent, positive values mean the part got slower.
+    /// `None` if the part has no duration in the current run.
+    pub fn change_percent(&self) -> Option<f64> {
+        self.current_nanos.map(|current_nanos| {
+            (current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+        })
+    }
+
+    /// A part that had a duration in the baseline but none in the current run always counts as a regression.
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent()
+            .is_none_or(|change| change > threshold_percent)
+    }
+}
+
+/// Pair up all parts of `current` that have a timing in `baseline`.
+/// Parts without a duration in `current`, e.g. because they exceeded a limit, are kept as regressions.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartDelta> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| Some((timing, baseline.get(timing.day)?)))
+        .flat_map(|(timing, baseline_timing)| {
+            [1, 2].into_iter().filter_map(move |part| {
+                let current_part = timing.part(part);
+                Some(PartDelta {
+                    day: timing.day,
+                    part,
+                    baseline_nanos: parse_duration(baseline_timing.part(part)?)?,
+                    current_nanos: current_part.and_then(parse_duration),
+                    current_limit: current_part.and_then(|s| s.parse().ok()),
+                })
+            })
+        })
+        .filter(|delta| delta.baseline_nanos > 0.0)
+        .collect()
+}
+
+/// Print a table of deltas, flagging regressions above the threshold.
+pub fn print_table(deltas: &[PartDelta], threshold_percent: f64) {
+    println!(
+        "\n{ANSI_BOLD}{:<5} {:<5} {:>12} {:>12} {:>9}{ANSI_RESET}",
+        "Day", "Part", "Baseline", "Current", "Change"
+    );
+
+    for delta in deltas {
+        let flag = if delta.is_regression(threshold_percent) {
+            format!(" {ANSI_BOLD}regression{ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        let current = match (delta.current_nanos, delta.current_limit) {
+            (Some(nanos), _) => format_nanos(nanos),
+            (None, Some(limit)) => limit.to_string(),
+            (None, None) => "missing".into(),
+        };
+        let change = delta
+            .change_percent()
+            .map_or_else(|| "-".into(), |change| format!("{change:+.1}%"));
+
+        println!(
+            "{:<5} {:<5} {:>12} {:>12} {:>9}{flag}",
+            delta.day.to_string(),
+            delta.part,
+            format_nanos(delta.baseline_nanos),
+            current,
+            change,
+        );
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::{
+            records::Limit,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let baseline = Timings {
+            data: vec![
+                timing(day!(1), Some("10ms"), Some("1.0s")),
+                timing(day!(2), Some("5µs"), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("12ms"), Some("500ms")),
+                timing(day!(2), Some("5µs"), Some("1ms")),
+                timing(day!(3), Some("1ms"), None),
+            ],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].part, 1);
+        assert!((deltas[0].change_percent().unwrap() - 20.0).abs() < 1e-6);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(25.0));
+
+        assert_eq!(deltas[1].part, 2);
+        assert!((deltas[1].change_percent().unwrap() + 50.0).abs() < 1e-6);
+        assert!(!deltas[1].is_regression(0.0));
+
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].change_percent(), Some(0.0));
+    }
+
+    #[test]
+    fn keeps_parts_without_current_duration_as_regressions() {
+        let baseline = Timings {
+            data: vec![timing(day!(1), Some("10ms"), Some("1.0s"))],
+        };
+        let current = Timings {
+            data: vec![timing(day!(1), Some("TIMEOUT"), None)],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(deltas.len(), 2);
+
+        assert_eq!(deltas[0].current_nanos, None);
+        assert_eq!(deltas[0].current_limit, Some(Limit::Timeout));
+        assert!(deltas[0].is_regression(10.0));
+
+        assert_eq!(deltas[1].current_limit, None);
+        assert!(deltas[1].is_regression(10.0));
+    }
+}
@@ -0,0 +1,375 @@
+//! Render grids and simulation states to images, animations or the terminal.
+//!
+//! Solutions opt in by checking [`is_enabled`], which is true when the day runs with `--visualize` (PNG and GIF files in
+//! `data/images`) or `--visualize-terminal` (ANSI colours). Visualizations are always off in tests and while benchmarking.
+//!
+//! ```
+//! # use advent_of_code::grid::Grid;
+//! # use advent_of_code::visualize::{colors, Image};
+//! let grid = Grid::parse("#.\n.#", Some).unwrap();
+//! let image = Image::from_grid(&grid, |&c| if c == '#' { colors::WHITE } else { colors::BLACK });
+//! assert_eq!(image.to_png().unwrap()[1..4], *b"PNG");
+//! ```
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+use std::{env, fs, thread};
+
+use glam::IVec2;
+
+use crate::grid::Grid;
+use crate::template::PuzzleId;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub mod colors {
+    use super::Rgb;
+
+    pub const BLACK: Rgb = [0, 0, 0];
+    pub const WHITE: Rgb = [255, 255, 255];
+    pub const GRAY: Rgb = [128, 128, 128];
+    pub const RED: Rgb = [220, 50, 47];
+    pub const GREEN: Rgb = [0, 200, 0];
+    pub const BLUE: Rgb = [38, 139, 210];
+    pub const YELLOW: Rgb = [255, 215, 0];
+    pub const ORANGE: Rgb = [255, 140, 0];
+}
+
+/// Where visualizations go, selected on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// `--visualize`: PNG and GIF files in `data/images`.
+    Files,
+    /// `--visualize-terminal`: ANSI colours on stderr.
+    Terminal,
+}
+
+impl Target {
+    /// The command line flag that selects this target.
+    pub fn flag(self) -> &'static str {
+        match self {
+            Target::Files => "--visualize",
+            Target::Terminal => "--visualize-terminal",
+        }
+    }
+}
+
+/// The selected target, `None` if visualizations are off.
+pub fn target() -> Option<Target> {
+    let args: Vec<String> = env::args().collect();
+    // NOTE: benched parts run many times, so visualizations are skipped.
+    if args.iter().any(|x| x == "--time") {
+        return None;
+    }
+
+    if args.iter().any(|x| x == "--visualize-terminal") {
+        Some(Target::Terminal)
+    } else if args.iter().any(|x| x == "--visualize") {
+        Some(Target::Files)
+    } else {
+        None
+    }
+}
+
+/// Whether visualizations are on, check this before collecting frames.
+pub fn is_enabled() -> bool {
+    target().is_some()
+}
+
+/// Path of a visualization file, e.g. `data/images/2024-15-expanded.png`.
+pub fn image_path(puzzle: PuzzleId, name: &str, extension: &str) -> String {
+    format!("data/images/{puzzle}-{name}.{extension}")
+}
+
+fn write_file(path: &str, bytes: &[u8]) {
+    let result = fs::create_dir_all("data/images").and_then(|_| fs::write(path, bytes));
+    match result {
+        Ok(()) => eprintln!("🎄 Wrote visualization to \"{path}\"."),
+        Err(e) => eprintln!("Failed to write visualization \"{path}\": {e}"),
+    }
+}
+
+/// Show an image if visualizations are on: as `<name>.png`, or in the terminal.
+pub fn show(puzzle: PuzzleId, name: &str, image: &Image) {
+    match target() {
+        Some(Target::Files) => match image.to_png() {
+            Ok(png) => write_file(&image_path(puzzle, name, "png"), &png),
+            Err(e) => eprintln!("Failed to encode visualization `{name}`: {e}"),
+        },
+        Some(Target::Terminal) => eprint!("{}", image.to_ansi()),
+        None => {}
+    }
+}
+
+/// Show an animation if visualizations are on: as `<name>.gif`, or played in the terminal.
+pub fn show_animation(puzzle: PuzzleId, name: &str, animation: &Animation) {
+    match target() {
+        Some(Target::Files) => match animation.to_gif() {
+            Ok(gif) => write_file(&image_path(puzzle, name, "gif"), &gif),
+            Err(e) => eprintln!("Failed to encode visualization `{name}`: {e}"),
+        },
+        Some(Target::Terminal) => animation.play(),
+        None => {}
+    }
+}
+
+/// Write a visualization that is not an image, e.g. a graphviz graph, as `<name>.<extension>` if visualizations are on.
+pub fn show_file(puzzle: PuzzleId, name: &str, extension: &str, contents: &str) {
+    if is_enabled() {
+        write_file(&image_path(puzzle, name, extension), contents.as_bytes());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An RGB image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    size: IVec2,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// An image of `size` filled with `background`.
+    pub fn new(size: IVec2, background: Rgb) -> Self {
+        let size = size.max(IVec2::ZERO);
+        Self {
+            size,
+            pixels: vec![background; (size.x * size.y) as usize],
+        }
+    }
+
+    /// One pixel per cell, coloured by `palette`.
+    pub fn from_grid<T>(grid: &Grid<T>, palette: impl Fn(&T) -> Rgb) -> Self {
+        Self {
+            size: grid.size(),
+            pixels: grid.iter().map(|(_, cell)| palette(cell)).collect(),
+        }
+    }
+
+    pub fn size(&self) -> IVec2 {
+        self.size
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        let in_bounds = pos.cmpge(IVec2::ZERO).all() && pos.cmplt(self.size).all();
+        in_bounds.then(|| (pos.y * self.size.x + pos.x) as usize)
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<Rgb> {
+        self.index_of(pos).map(|index| self.pixels[index])
+    }
+
+    /// Colour a pixel, positions outside of the image are ignored.
+    pub fn set(&mut self, pos: IVec2, color: Rgb) {
+        if let Some(index) = self.index_of(pos) {
+            self.pixels[index] = color;
+        }
+    }
+
+    /// Every pixel becomes a square of `factor` pixels, small grids are hard to see otherwise.
+    pub fn scaled(&self, factor: u32) -> Self {
+        let factor = factor.max(1) as i32;
+        let mut scaled = Self::new(self.size * factor, colors::BLACK);
+        for y in 0..scaled.size.y {
+            for x in 0..scaled.size.x {
+                let pos = IVec2::new(x, y);
+                scaled.set(pos, self.get(pos / factor).unwrap());
+            }
+        }
+        scaled
+    }
+
+    /// Encode as PNG.
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.size.x as u32, self.size.y as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&data).map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(png)
+    }
+
+    /// Render with ANSI true colours, two pixels per character.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for y in (0..self.size.y).step_by(2) {
+            for x in 0..self.size.x {
+                let [r, g, b] = self.get(IVec2::new(x, y)).unwrap();
+                let [br, bg, bb] = self.get(IVec2::new(x, y + 1)).unwrap_or(colors::BLACK);
+                out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀"));
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Frames of a simulation.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: Duration,
+}
+
+impl Animation {
+    /// An empty animation that shows every frame for `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            frames: Vec::new(),
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Write every frame as `<dir>/<index>.png`, e.g. to turn them into a video.
+    pub fn save_frames(&self, dir: &str) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (index, frame) in self.frames.iter().enumerate() {
+            let png = frame.to_png().map_err(io::Error::other)?;
+            fs::write(format!("{dir}/{index:05}.png"), png)?;
+        }
+        Ok(())
+    }
+
+    /// Play the frames in the terminal, drawing every frame over the previous one.
+    pub fn play(&self) {
+        for (index, frame) in self.frames.iter().enumerate() {
+            if index > 0 {
+                let lines = (self.frames[index - 1].size.y + 1) / 2;
+                eprint!("\x1b[{lines}A");
+            }
+            eprint!("{}", frame.to_ansi());
+            thread::sleep(self.delay);
+        }
+    }
+
+    /// Encode as a looping GIF. All frames must have the size of the first frame and at most 256 colours in total.
+    pub fn to_gif(&self) -> Result<Vec<u8>, String> {
+        let Some(first) = self.frames.first() else {
+            return Err("an animation needs at least one frame".into());
+        };
+        if self.frames.iter().any(|frame| frame.size != first.size) {
+            return Err("all frames must have the same size".into());
+        }
+        if first.size.x > u16::MAX as i32 || first.size.y > u16::MAX as i32 {
+            return Err(format!("frames of size {} are too large", first.size));
+        }
+
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut indices: HashMap<Rgb, u8> = HashMap::new();
+        for pixel in self.frames.iter().flat_map(|frame| &frame.pixels) {
+            if !indices.contains_key(pixel) {
+                if palette.len() == 256 {
+                    return Err("animations can have at most 256 colours".into());
+                }
+                indices.insert(*pixel, palette.len() as u8);
+                palette.push(*pixel);
+            }
+        }
+
+        let [width, height] = [first.size.x as u16, first.size.y as u16];
+        let delay = (self.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        let palette: Vec<u8> = palette.into_iter().flatten().collect();
+
+        let mut gif = Vec::new();
+        {
+            let mut encoder =
+                gif::Encoder::new(&mut gif, width, height, &palette).map_err(|e| e.to_string())?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(|e| e.to_string())?;
+
+            for frame in &self.frames {
+                let pixels = frame.pixels.iter().map(|pixel| indices[pixel]).collect();
+                let frame = gif::Frame {
+                    width,
+                    height,
+                    delay,
+                    buffer: Cow::Owned(pixels),
+                    ..gif::Frame::default()
+                };
+                encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(gif)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use glam::IVec2;
+
+    use super::{colors, Animation, Image};
+    use crate::grid::Grid;
+
+    #[test]
+    fn renders_grids() {
+        let grid = Grid::parse("#.\n.#\n..", Some).unwrap();
+        let image = Image::from_grid(&grid, |&c| match c {
+            '#' => colors::WHITE,
+            _ => colors::BLACK,
+        });
+
+        assert_eq!(image.get(IVec2::new(1, 1)), Some(colors::WHITE));
+        assert_eq!(image.scaled(2).size(), IVec2::new(4, 6));
+        assert_eq!(image.scaled(2).get(IVec2::new(3, 3)), Some(colors::WHITE));
+        assert_eq!(image.to_ansi().lines().count(), 2);
+
+        let png = image.to_png().unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (2, 3));
+        assert_eq!(data[..6], [255, 255, 255, 0, 0, 0]);
+        assert_eq!(data[9..12], [255, 255, 255]);
+    }
+
+    #[test]
+    fn encodes_animations() {
+        let mut animation = Animation::new(Duration::from_millis(100));
+        assert!(animation.to_gif().is_err());
+
+        for step in 0..3 {
+            let mut frame = Image::new(IVec2::new(3, 2), colors::BLACK);
+            frame.set(IVec2::new(step, 1), colors::RED);
+            animation.push(frame);
+        }
+
+        let gif = animation.to_gif().unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (3, 2));
+
+        for expected in animation.frames() {
+            let frame = decoder.read_next_frame().unwrap().unwrap();
+            assert_eq!(frame.delay, 10);
+            let pixels: Vec<_> = frame.buffer.chunks(4).map(|p| [p[0], p[1], p[2]]).collect();
+            assert_eq!(pixels, expected.pixels);
+        }
+        assert!(decoder.read_next_frame().unwrap().is_none());
+
+        animation.push(Image::new(IVec2::ONE, colors::BLACK));
+        assert!(animation.to_gif().is_err());
+    }
+}